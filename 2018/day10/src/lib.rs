@@ -1,3 +1,5 @@
+use aoc_tools::ocr::PixelGrid;
+
 aoc_tools::aoc_sol!(day10 2018: part1, part2);
 aoc_tools::pos!(Scalar; +y => D);
 
@@ -20,17 +22,6 @@ impl Star {
         let y = stars.iter().map(|s| s.pos.y as i64).sum::<i64>();
         Pos { x: (x / stars.len() as i64) as Scalar, y: (y / stars.len() as i64) as Scalar }
     }
-    pub fn range(stars: &[Star]) -> (Pos, Pos) {
-        stars.iter()
-            .map(|s| s.pos)
-            .fold(
-                (Pos { x: Scalar::MAX, y: Scalar::MAX }, Pos { x: Scalar::MIN, y: Scalar::MIN }),
-                |(min, max), pos| (
-                    Pos { x: min.x.min(pos.x), y: min.y.min(pos.y) },
-                    Pos { x: max.x.max(pos.x), y: max.y.max(pos.y) },
-                )
-            )
-    }
 }
 impl Debug for Star {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -74,28 +65,14 @@ fn get_message_time(initial: &[Star]) -> usize {
     max_vertical_line_score.1
 }
 
-pub fn part1(input: &str) -> String {
+pub fn part1(input: &str) -> PixelGrid {
     let mut stars = parse_input(input);
     let message_time = get_message_time(&stars);
     for star in &mut stars {
         star.step_n(message_time);
     }
 
-    let stars_range = Star::range(&stars);
-    let mut output = "\n".to_string();
-    for y in stars_range.0.y-1..=stars_range.1.y+1 {
-        for x in stars_range.0.x-2..=stars_range.1.x+2 {
-            let pos = Pos { x, y };
-            if stars.iter().any(|s| s.pos == pos) {
-                output.push('#');
-            } else {
-                output.push('.');
-            }
-        }
-        output.push('\n');
-    }
-    output.pop();
-    output
+    PixelGrid::from_points(stars.iter().map(|star| (star.pos.x, star.pos.y)))
 }
 
 pub fn part2(input: &str) -> usize {
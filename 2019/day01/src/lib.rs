@@ -0,0 +1,26 @@
+aoc_tools::aoc_sol!(day01 2019: part1, part2);
+
+pub fn part1(input: &str) -> usize {
+    let lines = parse_input(input);
+
+    lines.into_iter().map(|mass| mass / 3 - 2).sum::<usize>()
+}
+
+pub fn part2(input: &str) -> usize {
+    let lines = parse_input(input);
+
+    lines.into_iter().map(calc_fuel).sum::<usize>()
+}
+
+
+fn parse_input(input: &str) -> Vec<usize> {
+    input.lines()
+        .map(|line| line.parse().unwrap())
+        .collect()
+}
+
+fn calc_fuel(v: usize) -> usize {
+    if v == 0 { return 0 }
+    let fuel = (v / 3).saturating_sub(2);
+    fuel + calc_fuel(fuel)
+}
@@ -0,0 +1,45 @@
+use intcode_2019::{ parse_program, Machine, Memory };
+
+aoc_tools::aoc_sol!(day02 2019: part1, part2);
+
+pub fn part1(input: &str) -> isize {
+    let mut machine = Machine::new(parse_program(input), vec![]);
+    restore_gravity_assist(&mut machine.memory);
+
+    let _ = machine.run_until_halt();
+
+    machine.memory[0]
+}
+
+pub fn part2(input: &str) -> isize {
+    let start_data = parse_program(input);
+
+    let (noun, verb) = 'out: {
+        for noun in 0..99 {
+            for verb in 0..99 {
+                let mut machine = Machine::new(start_data.as_slice(), vec![]);
+                restore_gravity_assist_custom(&mut machine.memory, noun, verb);
+
+                let _ = machine.run_until_halt();
+
+                if machine.memory[0] == 19690720 {
+                    break 'out (noun, verb);
+                }
+            }
+        }
+
+        panic!("No solution found");
+    };
+
+    100 * noun + verb
+}
+
+pub fn restore_gravity_assist(data: &mut Memory) {
+    data[1] = 12;
+    data[2] = 2;
+}
+
+pub fn restore_gravity_assist_custom(data: &mut Memory, noun: isize, verb: isize) {
+    data[1] = noun;
+    data[2] = verb;
+}
@@ -0,0 +1,20 @@
+use intcode_2019::{ parse_program, Machine };
+
+aoc_tools::aoc_sol!(day05 2019: part1, part2);
+
+pub fn part1(input: &str) -> isize {
+    let mut machine = Machine::new(parse_program(input), vec![1]);
+
+    machine.run_until_halt().unwrap();
+
+    *machine.output.last().unwrap()
+}
+
+
+pub fn part2(input: &str) -> isize {
+    let mut machine = Machine::new(parse_program(input), vec![5]);
+
+    machine.run_until_halt().unwrap();
+    
+    *machine.output.last().unwrap()
+}
@@ -0,0 +1,114 @@
+use aoc_tools::graph::DiGraph;
+
+aoc_tools::aoc_sol!(day06 2019: part1, part2);
+
+pub fn part1(input: &str) -> usize {
+    let system = parse_input(input);
+
+    system.orbits()
+}
+
+pub fn part2(input: &str) -> usize {
+    let system = parse_input(input);
+
+    let from = system.orbit_center_for(Body::YOU).unwrap();
+    let to = system.orbit_center_for(Body::SAN).unwrap();
+
+    system.bfs_len(from, to)
+}
+
+
+fn parse_input(input: &str) -> System {
+    let mut system = System::new();
+    input.lines()
+        .map(|orbit| orbit.split_once(')').unwrap())
+        .map(|(center, orbiting_body)| (Body::from_str(center), Body::from_str(orbiting_body)))
+        .for_each(|(center, orbiting_body)| system.add_orbit(center, orbiting_body));
+
+    system
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Body(char, char, char);
+impl Body {
+    const COM: Self = Self('C', 'O', 'M');
+    const SAN: Self = Self('S', 'A', 'N');
+    const YOU: Self = Self('Y', 'O', 'U');
+
+    pub fn from_str(str: &str) -> Self {
+        let c0 = str.chars().next().unwrap_or('_');
+        let c1 = str.chars().nth(1).unwrap_or('_');
+        let c2 = str.chars().nth(2).unwrap_or('_');
+
+        Self(c0, c1, c2)
+    }
+}
+impl Debug for Body {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.0, self.1, self.2)
+    }
+}
+
+/// Edges go from a center to the bodies orbiting it
+struct System {
+    orbits: DiGraph<Body, (), ()>,
+}
+
+impl System {
+    pub fn new() -> Self {
+        let mut orbits = DiGraph::new();
+        orbits.insert_node(Body::COM, ());
+        Self { orbits }
+    }
+    pub fn add_orbit(&mut self, center: Body, orbiting_body: Body) {
+        if self.orbits.get_node(&center).is_none() {
+            self.orbits.insert_node(center, ());
+        }
+        if self.orbits.get_node(&orbiting_body).is_none() {
+            self.orbits.insert_node(orbiting_body, ());
+        }
+        self.orbits.insert_edge(&center, &orbiting_body, ());
+    }
+
+    pub fn orbits_for(&self, body: Body) -> usize {
+        let mut orbits = 0;
+        let mut curr = body;
+
+        while let Some(center) = self.orbit_center_for(curr) {
+            curr = center;
+            orbits += 1;
+        }
+
+        orbits
+    }
+    pub fn orbits(&self) -> usize {
+        let mut orbits = 0;
+        for (&body, _) in self.orbits.nodes() {
+            orbits += self.orbits_for(body);
+        }
+        orbits
+    }
+
+    pub fn bfs_len(&self, from: Body, to: Body) -> usize {
+        let mut curr_bodies: HashSet<_> = [from].into_iter().collect();
+        let mut new_bodies = HashSet::new();
+        let mut len = 0;
+
+        while !curr_bodies.contains(&to) {
+            for body in curr_bodies {
+                new_bodies.extend(self.orbits.predecessors(&body));
+                new_bodies.extend(self.orbits.successors(&body));
+            }
+
+            curr_bodies = new_bodies;
+            new_bodies = HashSet::new();
+            len += 1;
+        }
+
+        len
+    }
+
+    pub fn orbit_center_for(&self, body: Body) -> Option<Body> {
+        self.orbits.predecessors(&body).next()
+    }
+}
@@ -0,0 +1,60 @@
+use intcode_2019::{ parse_program, Machine };
+
+aoc_tools::aoc_sol!(day07 2019: part1, part2);
+
+fn perm5() -> Vec<[u8; 5]> {
+    fn perm5_recursive(output: &mut Vec<[u8; 5]>, curr: &mut [u8; 5], i: u8) {
+        for j in 0..5 {
+            if curr[0..i as usize].contains(&j) { continue }
+            curr[i as usize] = j;
+            if i == 4 {
+                output.push(*curr);
+            } else {
+                perm5_recursive(output, curr, i + 1);
+            }
+        }
+    }
+    let mut output = Vec::with_capacity(120);
+    perm5_recursive(&mut output, &mut [0; 5], 0);
+    output
+}
+
+fn run_value(program: &[isize], phase_settings: [u8; 5]) -> isize {
+    let mut machines: [_; 5] = std::array::from_fn(|i| Machine::new(program, vec![phase_settings[i] as isize]));
+
+    let mut next = 0;
+    for step in 0.. {
+        let i = step % 5;
+        let machine = machines.get_mut(i).unwrap();
+        machine.feed([next]);
+        match machine.run_until_output() {
+            Ok(output) => next = output,
+            Err(_) => return next,
+        }
+    }
+    unreachable!("We somehow counted to 2^64");
+}
+
+pub fn part1(input: &str) -> isize {
+    let data = parse_program(input);
+
+    let mut max_output = 0;
+    for perm in perm5() {
+        let output = run_value(&data, perm);
+        max_output = max_output.max(output);
+    }
+
+    max_output
+}
+
+pub fn part2(input: &str) -> isize {
+    let data = parse_program(input);
+
+    let mut max_output = 0;
+    for perm in perm5() {
+        let output = run_value(&data, std::array::from_fn(|i| perm[i] + 5));
+        max_output = max_output.max(output);
+    }
+
+    max_output
+}
@@ -1,3 +1,5 @@
+use aoc_tools::ocr::PixelGrid;
+
 aoc_tools::aoc_sol!(day08 2019: part1, part2);
 aoc_tools::map_struct!(Layer of Pixel, pos i16);
 
@@ -32,7 +34,7 @@ pub fn part1(input: &str) -> usize {
     ones * twos
 }
 
-pub fn part2(input: &str) -> i64 {
+pub fn part2(input: &str) -> PixelGrid {
     let layers = parse_input(input);
     let mut output = layers[0].clone();
     for layer in &layers[1..] {
@@ -44,9 +46,7 @@ pub fn part2(input: &str) -> i64 {
             }
         }
     }
-    // TODO: Decode it into a `String`
-    println!("{output:?}");
-    0
+    PixelGrid::from_rows(output.rows.iter().map(|row| row.iter().map(|&pixel| pixel == Pixel::WHITE)))
 }
 
 const WIDTH: usize = 25;
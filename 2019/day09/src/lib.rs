@@ -0,0 +1,19 @@
+use intcode_2019::{ parse_program, Machine };
+
+aoc_tools::aoc_sol!(day09 2019: part1, part2);
+
+pub fn part1(input: &str) -> isize {
+    let mut machine = Machine::new(parse_program(input), vec![1]);
+
+    machine.run_until_halt().unwrap();
+
+    machine.output[0]
+}
+
+pub fn part2(input: &str) -> isize {
+    let mut machine = Machine::new(parse_program(input), vec![2]);
+
+    machine.run_until_halt().unwrap();
+
+    machine.output[0]
+}
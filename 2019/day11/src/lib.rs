@@ -1,3 +1,4 @@
+use aoc_tools::ocr::PixelGrid;
 use intcode_2019::Machine;
 
 type Scalar = i32;
@@ -9,7 +10,6 @@ aoc_tools::pos!(Scalar; +y => D);
 enum Color { B = 0, W = 1 }
 
 struct PaintingRobot {
-    data: Vec<isize>,
     machine: Machine,
     pos: Pos,
     dir: Pos,
@@ -17,24 +17,20 @@ struct PaintingRobot {
 impl PaintingRobot {
     pub fn new(data: Vec<isize>) -> Self {
         Self {
-            data,
-            machine: Machine::new(vec![]),
+            machine: Machine::new(data, vec![]),
             pos: Pos::ZERO,
             dir: Pos::U,
         }
     }
     pub fn do_step(&mut self, map: &mut Map) -> bool {
-        self.machine.input.0.push(map.get(self.pos) as isize);
-        while self.machine.output.len() < 2 {
-            if self.machine.step(&mut self.data).is_err() {
-                return false;
-            }
-        }
-        let paint = if self.machine.output[0] == 1 { Color::W } else { Color::B };
+        self.machine.feed([map.get(self.pos) as isize]);
+        let (Ok(paint), Ok(turn)) = (self.machine.run_until_output(), self.machine.run_until_output()) else {
+            return false;
+        };
+        let paint = if paint == 1 { Color::W } else { Color::B };
         map.set(self.pos, paint);
 
-        self.dir = if self.machine.output[1] != 0 { self.dir.turn_r() } else { self.dir.turn_l() };
-        self.machine.output.clear();
+        self.dir = if turn != 0 { self.dir.turn_r() } else { self.dir.turn_l() };
 
         self.pos = self.pos.add(self.dir);
         true
@@ -92,17 +88,16 @@ pub fn part1(input: &str) -> usize {
     map.tiles.len()
 }
 
-pub fn part2(input: &str) -> usize {
+pub fn part2(input: &str) -> PixelGrid {
     let data = parse_input(input);
     let mut map = Map::new(Color::W);
     let mut robot = PaintingRobot::new(data.clone());
     while robot.do_step(&mut map) {}
 
-    // TODO: Decode it into a `String`
-    println!("{map:?}");
-    0
+    let white = map.tiles.iter().filter(|(_, &color)| color == Color::W);
+    PixelGrid::from_points(white.map(|(pos, _)| (pos.x, pos.y)))
 }
 
 fn parse_input(input: &str) -> Vec<isize> {
-    intcode_2019::parse_program(input, 512)
+    intcode_2019::parse_program(input)
 }
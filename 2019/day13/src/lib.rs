@@ -60,14 +60,9 @@ impl Debug for Tile {
     }
 }
 
-fn do_step(machine: &mut Machine, data: &mut [isize], map: &mut Map, first_frame: bool) -> Result<(Option<isize>, bool), String> {
-    while machine.output.len() < 3 {
-        machine.step(data).map_err(|_| "Invalid instruction".to_string())?;
-    }
-    let x = machine.output[0];
-    let y = machine.output[1];
-    let tile = machine.output[2];
-    machine.output.clear();
+fn do_step(machine: &mut Machine, map: &mut Map, first_frame: bool) -> Result<(Option<isize>, bool), String> {
+    let mut next = || machine.run_until_output().map_err(|state| format!("Machine stopped: {state:?}"));
+    let (x, y, tile) = (next()?, next()?, next()?);
 
     if (x, y) == (-1, 0) {
         Ok((Some(tile), false))
@@ -77,10 +72,10 @@ fn do_step(machine: &mut Machine, data: &mut [isize], map: &mut Map, first_frame
         Ok((None, if first_frame { pos == BOTTOM_RIGHT } else { tile == 4 }))
     }
 }
-fn do_frame(machine: &mut Machine, data: &mut [isize], map: &mut Map, first_frame: bool) -> Result<Option<isize>, (Option<isize>, String)> {
+fn do_frame(machine: &mut Machine, map: &mut Map, first_frame: bool) -> Result<Option<isize>, (Option<isize>, String)> {
     let mut score = None;
     loop {
-        let (new_score, is_frame_end) = do_step(machine, data, map, first_frame).map_err(|e| (score, e))?;
+        let (new_score, is_frame_end) = do_step(machine, map, first_frame).map_err(|e| (score, e))?;
         if new_score.is_some() {
             score = new_score;
         }
@@ -94,19 +89,13 @@ const HEIGHT: usize = 24;
 const BOTTOM_RIGHT: Pos = Pos { x: WIDTH as Scalar - 1, y: HEIGHT as Scalar - 1 };
 
 pub fn part1(input: &str) -> usize {
-    let mut data = parse_input(input);
-    let mut machine = Machine::new(vec![]);
+    let mut machine = Machine::new(parse_input(input), vec![]);
     let mut map = Map { rows: vec![vec![Tile::Empty; WIDTH]; HEIGHT] };
 
     loop {
-        while !machine.halt && machine.output.len() < 3 {
-            if machine.step(&mut data).is_err() { break }
-        }
-        if machine.output.len() < 3 { break }
-        let x = machine.output[0];
-        let y = machine.output[1];
-        let tile = machine.output[2];
-        machine.output.clear();
+        let (Ok(x), Ok(y), Ok(tile)) = (machine.run_until_output(), machine.run_until_output(), machine.run_until_output()) else {
+            break;
+        };
 
         let pos = Pos { x: x as Scalar, y: y as Scalar };
         *map.get_mut_raw(pos).unwrap() = Tile::from_int(tile as u8);
@@ -116,22 +105,20 @@ pub fn part1(input: &str) -> usize {
 }
 
 pub fn part2(input: &str) -> isize {
-    let mut data = parse_input(input);
-    data[0] = 2;
-    let mut machine = Machine::new(vec![]);
+    let mut machine = Machine::new(parse_input(input), vec![]);
+    machine.memory[0] = 2;
     let mut map = Map { rows: vec![vec![Tile::Empty; WIDTH]; HEIGHT] };
 
-    do_frame(&mut machine, &mut data, &mut map, true).unwrap();
+    do_frame(&mut machine, &mut map, true).unwrap();
 
     'score: loop {
         let mut total_moves = 0;
         let target = {
-            let mut temp_data = data.clone();
             let mut temp_machine = machine.clone();
             let mut temp_map = map.clone();
             temp_machine.input.0.push(0);
             loop {
-                match do_frame(&mut temp_machine, &mut temp_data, &mut temp_map, false) {
+                match do_frame(&mut temp_machine, &mut temp_map, false) {
                     Ok(_) => (),
                     Err((Some(final_score), _)) => break 'score final_score,
                     Err((None, err)) => panic!("Error when processing frame: {err}"),
@@ -156,7 +143,7 @@ pub fn part2(input: &str) -> isize {
         }
 
         loop {
-            do_frame(&mut machine, &mut data, &mut map, false).unwrap();
+            do_frame(&mut machine, &mut map, false).unwrap();
             if map.ball_pos().y == 20 { break }
             // println!("{map:?}");
             // std::thread::sleep(std::time::Duration::from_millis(20));
@@ -165,5 +152,5 @@ pub fn part2(input: &str) -> isize {
 }
 
 fn parse_input(input: &str) -> Vec<isize> {
-    intcode_2019::parse_program(input, 4096)
+    intcode_2019::parse_program(input)
 }
@@ -73,14 +73,12 @@ impl Debug for Map {
 #[derive(Clone)]
 struct Robot {
     machine: Machine,
-    data: Vec<isize>,
     pos: Pos
 }
 impl Robot {
     pub fn new(data: Vec<isize>) -> Self {
         Self {
-            machine: Machine::new(vec![]),
-            data,
+            machine: Machine::new(data, vec![]),
             pos: Pos::ZERO,
         }
     }
@@ -91,13 +89,9 @@ impl Robot {
             else if direction == Pos::W { 3 }
             else if direction == Pos::E { 4 }
             else { panic!("Invalid direction") };
-        self.machine.input.0.push(input);
-        while self.machine.output.is_empty() {
-            if self.machine.step(&mut self.data).is_err() {
-                return Err("Machine halted".to_string());
-            }
-        }
-        match self.machine.output.pop().unwrap() {
+        self.machine.feed([input]);
+        let status = self.machine.run_until_output().map_err(|state| format!("Machine stopped: {state:?}"))?;
+        match status {
             0 => Ok(None),
             1 => Ok(Some(false)),
             2 => Ok(Some(true)),
@@ -148,5 +142,5 @@ pub fn part2(input: &str) -> i16 {
 }
 
 fn parse_input(input: &str) -> Vec<isize> {
-    intcode_2019::parse_program(input, 0)
+    intcode_2019::parse_program(input)
 }
@@ -1,4 +1,7 @@
-aoc_tools::aoc_sol!(day16 2019: part1, part2);
+aoc_tools::aoc_sol!(day16 2019: part1, part2; params: Params; examples: first, four_phases);
+aoc_tools::params! {
+    phases: usize = 100;
+}
 
 type Scalar = i32;
 
@@ -44,7 +47,7 @@ pub fn to_num(digits: &[Scalar]) -> Scalar {
     digits.iter().fold(0, |num, digit| num * 10 + digit)
 }
 
-pub fn part1(input: &str) -> Scalar {
+pub fn part1(input: &str, params: &Params) -> Scalar {
     let mut input = parse_input(input);
 
     let ranges: Vec<Vec<_>> = (0..input.len())
@@ -55,7 +58,7 @@ pub fn part1(input: &str) -> Scalar {
         .map(|range| range.collect())
         .collect();
 
-    for _ in 0..100 {
+    for _ in 0..params.phases {
         let sections = Sections::from_inputs(&input);
         let mut output = vec![];
         for output_digit_idx in 0..input.len() {
@@ -84,7 +87,7 @@ pub fn part2rangesgen(offset: usize, len: usize) -> Vec<Vec<((usize, usize), Sca
         .collect()
 }
 
-pub fn part2(input: &str) -> Scalar {
+pub fn part2(input: &str, params: &Params) -> Scalar {
     let input = parse_input(input);
     let base_len = input.len();
     let offset = to_num(&input[0..7]) as usize;
@@ -100,7 +103,7 @@ pub fn part2(input: &str) -> Scalar {
 
     let ranges: Vec<Vec<_>> = part2rangesgen(offset, base_len * 10_000);
 
-    for i in 0..100 {
+    for _ in 0..params.phases {
         let sections = Sections::from_inputs(&input);
         let mut output = vec![];
         for output_digit_idx in 0..input.len() {
@@ -191,19 +191,17 @@ impl Map {
     }
 }
 
-fn get_map(machine: &mut Machine, data: &mut [isize]) -> Map {
+fn get_map(machine: &mut Machine) -> Map {
     let mut map = String::new();
-    while machine.step(data).is_ok() {
-        let Some(byte) = machine.output.pop() else { continue };
+    while let Ok(byte) = machine.run_until_output() {
         map.push(byte as u8 as char);
     }
     Map { rows: aoc_tools::parse_map(&map, Tile::parse) }
 }
 
 pub fn part1(input: &str) -> Scalar {
-    let mut data = parse_input(input);
-    let mut machine = Machine::new(vec![]);
-    let map = get_map(&mut machine, &mut data);
+    let mut machine = Machine::new(parse_input(input), vec![]);
+    let map = get_map(&mut machine);
     let mut sum = 0;
     for intersection in map.find_intersections() {
         sum += intersection.x * intersection.y;
@@ -212,11 +210,10 @@ pub fn part1(input: &str) -> Scalar {
 }
 
 pub fn part2(input: &str) -> isize {
-    let mut data = parse_input(input);
-    let mut machine = Machine::new(vec![]);
-    let map = get_map(&mut machine.clone(), &mut data.clone());
+    let mut machine = Machine::new(parse_input(input), vec![]);
+    let map = get_map(&mut machine.clone());
 
-    data[0] = 2;
+    machine.memory[0] = 2;
 
     let directions = map.get_full_directions();
     let functions = Move::get_abc(&directions);
@@ -245,11 +242,11 @@ pub fn part2(input: &str) -> isize {
     machine.input.0.push(b'n' as isize);
     machine.input.0.push(b'\n' as isize);
 
-    while machine.step(&mut data).is_ok() {}
+    let _ = machine.run_until_halt();
 
     machine.output.pop().unwrap()
 }
 
 fn parse_input(input: &str) -> Vec<isize> {
-    intcode_2019::parse_program(input, 4096)
+    intcode_2019::parse_program(input)
 }
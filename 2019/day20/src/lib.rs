@@ -1,4 +1,4 @@
-use aoc_tools::graph::Graph;
+use aoc_tools::graph::{Graph, GraphSearch};
 
 type Scalar = i8;
 
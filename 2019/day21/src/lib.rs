@@ -0,0 +1,53 @@
+use intcode_2019::{ parse_program, Machine };
+
+aoc_tools::aoc_sol!(day21 2019: part1, part2);
+
+const SPRINGDROID_PROGRAM_P1: &str = r#"
+NOT A J
+NOT B T
+AND T J
+NOT C T
+AND T J
+NOT D T
+AND T J
+NOT A T
+OR A T
+AND A T
+AND B T
+AND C T
+NOT T T
+AND D T
+OR T J
+WALK
+"#;
+
+const SPRINGDROID_PROGRAM_P2: &str = r#"
+NOT A J
+NOT A T
+OR A T
+AND A T
+AND B T
+AND C T
+NOT T T
+AND D T
+AND H T
+OR T J
+RUN
+"#;
+
+pub fn part1(input: &str) -> isize {
+    let mut machine = Machine::new_ascii(parse_program(input), SPRINGDROID_PROGRAM_P1.trim_start());
+
+    machine.run_until_halt().unwrap_or_else(|state| panic!("{state:?}"));
+
+    *machine.output.last().unwrap()
+}
+
+
+pub fn part2(input: &str) -> isize {
+    let mut machine = Machine::new_ascii(parse_program(input), SPRINGDROID_PROGRAM_P2.trim_start());
+
+    machine.run_until_halt().unwrap_or_else(|state| panic!("{state:?}"));
+
+    *machine.output.last().unwrap()
+}
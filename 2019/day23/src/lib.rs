@@ -0,0 +1,103 @@
+aoc_tools::aoc_sol!(day23 2019: part1, part2);
+
+use intcode_2019::{ parse_program, Machine, RunState };
+
+/// A networked computer, with the packet it's partway through sending
+struct Nic {
+    machine: Machine,
+    sending: Vec<isize>,
+    /// Times in a row it's wanted input and had none
+    idle_reads: u32,
+}
+
+impl Nic {
+    fn new(program: &[isize], addr: isize) -> Self {
+        Self { machine: Machine::new(program, vec![addr]), sending: vec![], idle_reads: 0 }
+    }
+
+    /// Runs until the next event. Returns a packet once it's been sent in full.
+    fn run(&mut self) -> Option<(isize, isize, isize)> {
+        match self.machine.run_until_event() {
+            RunState::NeedsInput => {
+                self.machine.feed([-1]);
+                self.idle_reads += 1;
+            },
+            RunState::Output(val) => {
+                self.idle_reads = 0;
+                self.sending.push(val);
+                if let [addr, x, y] = self.sending[..] {
+                    self.sending.clear();
+                    return Some((addr, x, y));
+                }
+            },
+            RunState::Halted => (),
+            RunState::Error(e) => panic!("NIC failed: {e}"),
+        }
+        None
+    }
+
+    fn receive(&mut self, x: isize, y: isize) {
+        self.machine.feed([x, y]);
+        self.idle_reads = 0;
+    }
+
+    /// Has read nothing twice in a row without sending anything, or has halted
+    fn is_idle(&self) -> bool {
+        self.machine.halt || self.idle_reads >= 2
+    }
+}
+
+pub fn part1(input: &str) -> isize {
+    let data = parse_program(input);
+
+    let mut nics: Vec<Nic> = (0..50).map(|addr| Nic::new(&data, addr)).collect();
+
+    loop {
+        let packets: Vec<_> = nics.iter_mut().filter_map(Nic::run).collect();
+        for (addr, x, y) in packets {
+            if addr == 255 {
+                return y;
+            }
+            nics[addr as usize].receive(x, y);
+        }
+    }
+}
+
+pub fn part2(input: &str) -> isize {
+    let data = parse_program(input);
+
+    let mut nics: Vec<Nic> = (0..50).map(|addr| Nic::new(&data, addr)).collect();
+    
+    let mut seen_nat_packets = std::collections::HashSet::new();
+    let mut nat_packet = None;
+
+    loop {
+        let packets: Vec<_> = nics.iter_mut().filter_map(Nic::run).collect();
+        for (addr, x, y) in packets {
+            if addr == 255 {
+                nat_packet = Some((x, y));
+            } else {
+                nics[addr as usize].receive(x, y);
+            }
+        }
+
+        if nics.iter().all(Nic::is_idle) {
+            let (x, y) = nat_packet.expect("The network went idle before the NAT got a packet");
+            if !seen_nat_packets.insert((x, y)) {
+                return y;
+            }
+            nics[0].receive(x, y);
+        }
+    }
+}
+
+
+// fn parse_input(input: &str) -> Vec<isize> {
+//     let mut mem: Vec<_> = input.split(',')
+//         .map(|num| num.parse().unwrap())
+//         .collect();
+
+//     mem.extend([0; 256]);
+
+//     mem
+// }
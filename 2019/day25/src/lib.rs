@@ -0,0 +1,74 @@
+use intcode_2019::{ parse_program, Machine, RunState };
+
+aoc_tools::aoc_sol!(day25 2019: part1);
+
+const PROGRAM_INPUT: &str = "
+east
+take jam
+east
+take fuel cell
+west
+south
+take shell
+north
+west
+south
+west
+take easter egg
+north
+east
+take space heater
+west
+south
+west
+west
+take monolith
+south
+west
+north
+take coin
+south
+east
+north
+west
+take mug
+north
+";
+
+pub fn part1(input: &str) -> String {
+    let mut commands: String = PROGRAM_INPUT.trim_start().to_string();
+
+    let items = ["jam", "mug", "space heater", "fuel cell", "shell", "monolith", "easter egg", "coin"];
+    for i in 0..2_usize.pow(items.len() as u32) {
+        for bit in 0..items.len() {
+            if (i >> bit) & 1 == 1 {
+                commands.push_str("take ");
+            } else {
+                commands.push_str("drop ");
+            }
+            commands.push_str(items[bit]);
+            commands.push('\n');
+        }
+        commands.push_str("inv\n");
+        commands.push_str("north\n");
+    }
+
+    let mut machine = Machine::new_ascii(parse_program(input), &commands);
+    
+    let mut lines = vec![];
+
+    while machine.step().is_none_or(|state| matches!(state, RunState::Output(_))) {
+        if machine.output.last() == Some(&(b'\n' as isize)) {
+            let line = machine.drain_ascii();
+            let done = line.starts_with("\"Oh, hello!");
+            lines.push(line);
+            if done { break }
+        }
+    }
+
+    let last_line = lines.last().unwrap();
+    let (_, password) = last_line.split_once(" typing ").unwrap();
+    let (password, _) = password.split_once(" on ").unwrap();
+
+    password.to_string()
+}
@@ -0,0 +1,250 @@
+use std::io::{BufRead, Write};
+
+use intcode_2019::{parse_program, Debugger, Disassembly, Instruction, Machine, Memory, RunState, Stop};
+
+const USAGE: &str = "Usage: intcode-debug <program>";
+
+const HELP: &str = "\
+Addresses and values can be decimal or 0x hex. An empty line repeats the last command.
+    s, step [<n>]              Run <n> instructions, 1 by default
+    c, continue                Run until a breakpoint, watchpoint, halt, or the program wants input
+    b, break <addr>            Stop before running the instruction at <addr>
+    b, break op <opcode>       Stop before any instruction with <opcode>, by name (Inp) or number (3)
+    w, watch <addr>            Stop after <addr> changes
+    delete <addr> | op <opcode>
+                               Remove breakpoints and watchpoints
+    i, info                    List breakpoints, watchpoints and snapshots
+    r, regs                    Show pc, the relative offset, and the next instruction
+    x <addr> [<len>]           Show memory
+    set <addr> <value>...      Write values to memory from <addr> on
+    pc <addr>                  Jump to <addr>
+    d, dis [<addr>] [<n>]      Disassemble <n> instructions from <addr>, the pc by default
+    l, list                    List all the code reachable from 0 and the pc, with data in between
+    in <text>                  Feed the program a line of ASCII input
+    inraw <value>...           Feed the program raw values
+    save <name>                Snapshot the whole machine
+    restore <name>             Go back to a snapshot
+    h, help                    Show this
+    q, quit                    Leave";
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let (Some(path), None) = (args.next(), args.next()) else {
+        eprintln!("{USAGE}");
+        std::process::exit(2);
+    };
+    let program = match std::fs::read_to_string(&path) {
+        Ok(input) => parse_program(&input),
+        Err(e) => {
+            eprintln!("Couldn't read {path}: {e}");
+            std::process::exit(1);
+        },
+    };
+
+    let mut debugger = Debugger::new(Machine::new(program, vec![]));
+    let mut last_command = String::new();
+    let mut lines = std::io::stdin().lock().lines();
+    loop {
+        print!("(intcode) ");
+        std::io::stdout().flush().unwrap();
+        let Some(Ok(line)) = lines.next() else { break };
+        let line = if line.trim().is_empty() { last_command.clone() } else { line };
+
+        match command(&mut debugger, &line) {
+            Ok(true) => break,
+            Ok(false) => (),
+            Err(e) => println!("error: {e}"),
+        }
+        last_command = line;
+    }
+}
+
+/// Runs one line of input. `true` to quit.
+fn command(debugger: &mut Debugger, line: &str) -> Result<bool, String> {
+    let line = line.trim();
+    let (name, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let args: Vec<&str> = rest.split_whitespace().collect();
+    match (name, &args[..]) {
+        ("", _) => (),
+        ("s" | "step", []) => report(debugger, |debugger| debugger.step_n(1)),
+        ("s" | "step", [n]) => {
+            let n = parse_num(n)?;
+            report(debugger, |debugger| debugger.step_n(n))
+        },
+        ("c" | "continue", []) => report(debugger, Debugger::cont),
+        ("b" | "break", ["op", opcode]) => {
+            let opcode = parse_opcode(opcode)?;
+            debugger.opcode_breakpoints.insert(opcode);
+        },
+        ("b" | "break", [addr]) => {
+            debugger.breakpoints.insert(parse_num(addr)?);
+        },
+        ("w" | "watch", [addr]) => debugger.watch(parse_num(addr)?),
+        ("delete", ["op", opcode]) => {
+            let opcode = parse_opcode(opcode)?;
+            if !debugger.opcode_breakpoints.remove(&opcode) {
+                return Err(format!("No breakpoint on opcode {opcode}"));
+            }
+        },
+        ("delete", [addr]) => {
+            let addr = parse_num(addr)?;
+            let had_breakpoint = debugger.breakpoints.remove(&addr);
+            if !debugger.unwatch(addr) && !had_breakpoint {
+                return Err(format!("Nothing set at 0x{addr:04x}"));
+            }
+        },
+        ("i" | "info", []) => {
+            let breakpoints: Vec<String> = debugger.breakpoints.iter().map(|addr| format!("0x{addr:04x}")).collect();
+            let opcodes: Vec<String> = debugger.opcode_breakpoints.iter().map(|&op| opcode_name(op)).collect();
+            let watchpoints: Vec<String> = debugger.watchpoints().map(|addr| format!("0x{addr:04x}")).collect();
+            let snapshots: Vec<&str> = debugger.snapshots.keys().map(|name| name.as_str()).collect();
+            println!("breakpoints: {}", breakpoints.join(", "));
+            println!("opcode breakpoints: {}", opcodes.join(", "));
+            println!("watchpoints: {}", watchpoints.join(", "));
+            println!("snapshots: {}", snapshots.join(", "));
+        },
+        ("r" | "regs", []) => {
+            let machine = &debugger.machine;
+            println!("pc: 0x{:04x}  offset: {}  halted: {}", machine.pc, machine.offset, machine.halt);
+            println!("queued input: {}", machine.input.0.len() - machine.input.1);
+            print_next(machine);
+        },
+        ("x", [addr]) => dump(&debugger.machine, memory_range(parse_num(addr)?, 8)?),
+        ("x", [addr, len]) => dump(&debugger.machine, memory_range(parse_num(addr)?, parse_num(len)?)?),
+        ("set", [addr, values @ ..]) if !values.is_empty() => {
+            let values = values.iter().map(|v| parse_num(v)).collect::<Result<Vec<isize>, _>>()?;
+            let range = memory_range(parse_num(addr)?, values.len())?;
+            for (addr, val) in range.zip(values) {
+                debugger.machine.memory.set(addr, val);
+            }
+        },
+        ("pc", [addr]) => {
+            debugger.machine.pc = parse_num(addr)?;
+            print_next(&debugger.machine);
+        },
+        ("d" | "dis", _) if args.len() <= 2 => {
+            let addr = args.first().map_or(Ok(debugger.machine.pc), |addr| parse_num(addr))?;
+            let count = args.get(1).map_or(Ok(10), |count| parse_num(count))?;
+            disassemble(&debugger.machine, addr, count);
+        },
+        ("l" | "list", []) => {
+            let machine = &debugger.machine;
+            print!("{}", Disassembly::new(machine.memory.as_slice(), &[0, machine.pc]));
+        },
+        ("in", _) => {
+            debugger.machine.feed_ascii(rest);
+            debugger.machine.feed_ascii("\n");
+        },
+        ("inraw", values) if !values.is_empty() => {
+            let values = values.iter().map(|v| parse_num(v)).collect::<Result<Vec<isize>, _>>()?;
+            debugger.machine.feed(values);
+        },
+        ("save", [snapshot]) => debugger.save(snapshot),
+        ("restore", [snapshot]) => {
+            debugger.restore(snapshot)?;
+            print_next(&debugger.machine);
+        },
+        ("h" | "help", []) => println!("{HELP}"),
+        ("q" | "quit", []) => return Ok(true),
+        _ => return Err(format!("Couldn't understand `{line}`. Try `help`.")),
+    }
+    Ok(false)
+}
+
+/// Runs the machine, then shows what it printed and why it stopped
+fn report(debugger: &mut Debugger, run: impl FnOnce(&mut Debugger) -> Stop) {
+    let stop = run(debugger);
+    let output: String = debugger.machine.drain_output()
+        .map(|val| match u8::try_from(val) {
+            Ok(ch) if ch.is_ascii() => (ch as char).to_string(),
+            _ => format!("[{val}]"),
+        })
+        .collect();
+    print!("{output}");
+    if !output.is_empty() && !output.ends_with('\n') {
+        println!();
+    }
+    match stop {
+        Stop::Stepped => (),
+        Stop::Breakpoint(pc) => println!("Breakpoint at 0x{pc:04x}"),
+        Stop::Opcode(opcode) => println!("Breakpoint on {}", opcode_name(opcode)),
+        Stop::Watchpoint { addr, old, new } => println!("0x{addr:04x} changed from {old} to {new}"),
+        Stop::Machine(RunState::NeedsInput) => println!("Waiting for input"),
+        Stop::Machine(RunState::Halted) => println!("Halted"),
+        Stop::Machine(RunState::Error(e)) => {
+            // Already says where it is
+            println!("{e}");
+            return;
+        },
+        Stop::Machine(RunState::Output(_)) => unreachable!("Outputs don't stop the debugger"),
+    }
+    print_next(&debugger.machine);
+}
+
+fn print_next(machine: &Machine) {
+    if !machine.halt {
+        disassemble(machine, machine.pc, 1);
+    }
+}
+
+/// Decodes straight through from `addr`, so it can start mid-instruction. For
+/// a listing that follows jumps, see `Disassembly`.
+fn disassemble(machine: &Machine, addr: usize, count: usize) {
+    let mut machine = machine.clone();
+    machine.pc = addr;
+    for _ in 0..count {
+        match machine.decode() {
+            Ok(instruction) => {
+                println!("{:04x}    {instruction}", machine.pc);
+                machine.pc += instruction.size();
+            },
+            Err(e) => {
+                println!("{:04x}    {e}", machine.pc);
+                break;
+            },
+        }
+    }
+}
+
+/// `len` addresses from `addr`, if they're all within what memory can grow to
+fn memory_range(addr: usize, len: usize) -> Result<std::ops::Range<usize>, String> {
+    addr.checked_add(len)
+        .filter(|&end| end <= Memory::MAX_LEN)
+        .map(|end| addr..end)
+        .ok_or_else(|| format!("Addresses must be below 0x{:x}", Memory::MAX_LEN))
+}
+
+fn dump(machine: &Machine, range: std::ops::Range<usize>) {
+    for start in range.clone().step_by(8) {
+        let values: Vec<String> = (start..range.end.min(start + 8))
+            .map(|addr| machine.memory[addr].to_string())
+            .collect();
+        println!("{start:04x}    {}", values.join(", "));
+    }
+}
+
+fn parse_num<T: TryFrom<i64>>(num: &str) -> Result<T, String> {
+    let (negative, digits) = match num.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, num),
+    };
+    let val = match digits.strip_prefix("0x") {
+        Some(hex) => i64::from_str_radix(hex, 16),
+        None => digits.parse(),
+    }.map_err(|_| format!("Invalid number `{num}`"))?;
+    T::try_from(if negative { -val } else { val }).map_err(|_| format!("`{num}` is out of range"))
+}
+
+fn parse_opcode(opcode: &str) -> Result<isize, String> {
+    Instruction::OPCODES.iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(opcode))
+        .map(|&(_, int)| Ok(int))
+        .unwrap_or_else(|| parse_num(opcode))
+}
+
+fn opcode_name(opcode: isize) -> String {
+    Instruction::OPCODES.iter()
+        .find(|&&(_, int)| int == opcode)
+        .map_or_else(|| opcode.to_string(), |(name, _)| name.to_string())
+}
+
@@ -0,0 +1,157 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::machine::{Machine, RunState};
+
+/// Wraps a machine to stop it at interesting points, and keeps snapshots of it
+#[derive(Debug, Clone)]
+pub struct Debugger {
+    pub machine: Machine,
+    /// Stop before running the instruction at these addresses
+    pub breakpoints: BTreeSet<usize>,
+    /// Stop before running any instruction with these opcodes
+    pub opcode_breakpoints: BTreeSet<isize>,
+    /// Stop after these addresses change, with what they held last
+    watchpoints: BTreeMap<usize, isize>,
+    pub snapshots: BTreeMap<String, Machine>,
+}
+
+/// Why the debugger handed control back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// Ran as many instructions as asked
+    Stepped,
+    Breakpoint(usize),
+    Opcode(isize),
+    Watchpoint { addr: usize, old: isize, new: isize },
+    /// Something only the machine can report. Outputs don't stop it.
+    Machine(RunState),
+}
+
+impl Debugger {
+    pub fn new(machine: Machine) -> Self {
+        Self {
+            machine,
+            breakpoints: BTreeSet::new(),
+            opcode_breakpoints: BTreeSet::new(),
+            watchpoints: BTreeMap::new(),
+            snapshots: BTreeMap::new(),
+        }
+    }
+
+    pub fn watch(&mut self, addr: usize) {
+        self.watchpoints.insert(addr, self.machine.memory[addr]);
+    }
+    pub fn unwatch(&mut self, addr: usize) -> bool {
+        self.watchpoints.remove(&addr).is_some()
+    }
+    pub fn watchpoints(&self) -> impl Iterator<Item = usize> + '_ {
+        self.watchpoints.keys().copied()
+    }
+
+    /// Runs one instruction, ignoring breakpoints
+    pub fn step(&mut self) -> Option<Stop> {
+        let state = self.machine.step();
+        for (&addr, last) in &mut self.watchpoints {
+            let new = self.machine.memory[addr];
+            if new != *last {
+                let old = std::mem::replace(last, new);
+                return Some(Stop::Watchpoint { addr, old, new });
+            }
+        }
+        match state {
+            None | Some(RunState::Output(_)) => None,
+            Some(state) => Some(Stop::Machine(state)),
+        }
+    }
+
+    /// Runs up to `count` instructions, stopping early on anything worth a look
+    pub fn step_n(&mut self, count: usize) -> Stop {
+        for i in 0..count {
+            if i > 0 {
+                if let Some(stop) = self.breakpoint() { return stop }
+            }
+            if let Some(stop) = self.step() { return stop }
+        }
+        Stop::Stepped
+    }
+
+    /// Runs until something stops it. Doesn't stop for a breakpoint on the
+    /// instruction it starts at, so it can carry on from one.
+    pub fn cont(&mut self) -> Stop {
+        if let Some(stop) = self.step() { return stop }
+        loop {
+            if let Some(stop) = self.breakpoint() { return stop }
+            if let Some(stop) = self.step() { return stop }
+        }
+    }
+
+    /// Whether a breakpoint is on the next instruction
+    fn breakpoint(&self) -> Option<Stop> {
+        let pc = self.machine.pc;
+        let opcode = self.machine.memory[pc] % 100;
+        if self.breakpoints.contains(&pc) {
+            Some(Stop::Breakpoint(pc))
+        } else if self.opcode_breakpoints.contains(&opcode) {
+            Some(Stop::Opcode(opcode))
+        } else {
+            None
+        }
+    }
+
+    pub fn save(&mut self, name: &str) {
+        self.snapshots.insert(name.to_string(), self.machine.clone());
+    }
+    /// Puts the machine back how it was saved. Breakpoints stay as they are.
+    pub fn restore(&mut self, name: &str) -> Result<(), String> {
+        let snapshot = self.snapshots.get(name).ok_or_else(|| format!("No snapshot named `{name}`"))?;
+        self.machine = snapshot.clone();
+        for (&addr, last) in &mut self.watchpoints {
+            *last = self.machine.memory[addr];
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_debugger() {
+    // Calls a function at 0x10 that outputs, then echoes input forever from 9
+    let program = vec![109, 100, 21101, 9, 0, 0, 1105, 1, 16, 3, 30, 4, 30, 1105, 1, 9, 4, 20, 2106, 0, 0];
+
+    let mut debugger = Debugger::new(Machine::new(program.clone(), vec![]));
+    debugger.opcode_breakpoints.insert(4);
+    assert_eq!(debugger.cont(), Stop::Opcode(4));
+    assert_eq!(debugger.machine.pc, 16);
+    assert_eq!(debugger.step_n(2), Stop::Stepped);
+    assert_eq!(debugger.machine.pc, 9);
+
+    let mut debugger = Debugger::new(Machine::new(program, vec![]));
+    debugger.breakpoints.insert(9);
+    assert_eq!(debugger.cont(), Stop::Breakpoint(9));
+    assert_eq!(debugger.machine.offset, 100);
+    // Carries on from the breakpoint it's at
+    assert_eq!(debugger.cont(), Stop::Machine(RunState::NeedsInput));
+    debugger.machine.feed([65]);
+    assert_eq!(debugger.cont(), Stop::Breakpoint(9));
+    assert_eq!(debugger.machine.output, [0, 65]);
+    // Stepping stops for breakpoints too, but not the one it starts at
+    debugger.machine.feed([1, 2]);
+    assert_eq!(debugger.step_n(10), Stop::Breakpoint(9));
+    assert_eq!(debugger.machine.input.0[debugger.machine.input.1..], [2]);
+
+    debugger.breakpoints.clear();
+    debugger.watch(30);
+    assert_eq!(debugger.cont(), Stop::Watchpoint { addr: 30, old: 1, new: 2 });
+    assert_eq!(debugger.machine.pc, 11);
+    debugger.save("two");
+    debugger.machine.feed([3]);
+    assert_eq!(debugger.cont(), Stop::Watchpoint { addr: 30, old: 2, new: 3 });
+
+    // Restoring resyncs watchpoints to the restored memory
+    debugger.restore("two").unwrap();
+    assert_eq!((debugger.machine.pc, debugger.machine.memory[30]), (11, 2));
+    debugger.machine.feed([3]);
+    assert_eq!(debugger.cont(), Stop::Watchpoint { addr: 30, old: 2, new: 3 });
+    assert!(debugger.unwatch(30));
+    assert_eq!(debugger.cont(), Stop::Machine(RunState::NeedsInput));
+    assert!(debugger.restore("missing").is_err());
+}
@@ -0,0 +1,290 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+use std::ops::Range;
+
+use crate::instruction::{Addr, Instruction, Mode};
+use crate::machine::Machine;
+
+/// A program split into code and data by following its control flow from some
+/// entry points. Jumps to computed addresses can't be followed, except for
+/// returns from the usual calling convention: store the return address,
+/// jump, and later jump back through `$rel`.
+#[derive(Debug, Clone)]
+pub struct Disassembly {
+    program: Vec<isize>,
+    pub entry_points: Vec<usize>,
+    /// Runs of code only entered at the top, in address order
+    pub blocks: Vec<Block>,
+    /// Addresses jumped to directly
+    pub jump_targets: BTreeSet<usize>,
+    /// Addresses called as functions
+    pub functions: BTreeSet<usize>,
+    /// What's never reached as code, in address order
+    pub data: Vec<Range<usize>>,
+    /// Jumps that are calls, by address
+    calls: BTreeMap<usize, usize>,
+    /// Jumps back through `$rel`, by address
+    returns: BTreeSet<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub start: usize,
+    pub instructions: Vec<(usize, Instruction)>,
+    /// Where it can carry on from, where known. After a call, that's the
+    /// function and where it returns to.
+    pub successors: Vec<usize>,
+}
+
+impl Block {
+    /// Just past the last instruction
+    pub fn end(&self) -> usize {
+        self.instructions.last().map_or(self.start, |&(pc, instruction)| pc + instruction.size())
+    }
+}
+
+/// How an instruction passes control on
+enum Flow {
+    Next,
+    Jump(Addr),
+    Branch(Addr),
+    Halt,
+}
+
+impl Flow {
+    fn of(instruction: Instruction) -> Self {
+        match instruction {
+            | Instruction::Jit { cond: Addr { arg, mode: Mode::Immediate }, addr }
+            | Instruction::Jif { cond: Addr { arg, mode: Mode::Immediate }, addr } => {
+                let jumps = matches!(instruction, Instruction::Jit { .. }) == (arg != 0);
+                if jumps { Self::Jump(addr) } else { Self::Next }
+            },
+            Instruction::Jit { addr, .. } | Instruction::Jif { addr, .. } => Self::Branch(addr),
+            Instruction::Hlt {} => Self::Halt,
+            _ => Self::Next,
+        }
+    }
+}
+
+/// The constant an instruction stores, for the `Str` forms of `Add` and `Mul`
+fn stored_constant(instruction: Instruction) -> Option<isize> {
+    const fn imm(arg: isize) -> Addr {
+        Addr { arg, mode: Mode::Immediate }
+    }
+    match instruction {
+        | Instruction::Add { a: Addr { arg, mode: Mode::Immediate }, b, .. }
+        | Instruction::Add { b: Addr { arg, mode: Mode::Immediate }, a: b, .. } if b == imm(0) => Some(arg),
+        | Instruction::Mul { a: Addr { arg, mode: Mode::Immediate }, b, .. }
+        | Instruction::Mul { b: Addr { arg, mode: Mode::Immediate }, a: b, .. } if b == imm(1) => Some(arg),
+        _ => None,
+    }
+}
+
+impl Disassembly {
+    pub fn new(program: &[isize], entry_points: &[usize]) -> Self {
+        let mut machine = Machine::new(program, vec![]);
+        let mut instructions = BTreeMap::new();
+        let mut is_code = vec![false; program.len()];
+        let mut block_starts: BTreeSet<usize> = entry_points.iter().copied().collect();
+        let mut jump_targets = BTreeSet::new();
+        let mut functions = BTreeSet::new();
+        let mut calls = BTreeMap::new();
+        let mut returns = BTreeSet::new();
+
+        let mut to_visit = entry_points.to_vec();
+        while let Some(start) = to_visit.pop() {
+            machine.pc = start;
+            let mut prev = None;
+            while machine.pc < program.len() && !is_code[machine.pc] {
+                let pc = machine.pc;
+                let Ok(instruction) = machine.decode() else { break };
+                let end = pc + instruction.size();
+                if is_code[pc..end.min(program.len())].iter().any(|&code| code) { break }
+                is_code[pc..end.min(program.len())].fill(true);
+                instructions.insert(pc, instruction);
+                machine.pc = end;
+
+                match Flow::of(instruction) {
+                    Flow::Next => (),
+                    Flow::Halt => break,
+                    Flow::Jump(Addr { arg: target, mode: Mode::Immediate }) if target >= 0 => {
+                        let target = target as usize;
+                        jump_targets.insert(target);
+                        block_starts.insert(target);
+                        to_visit.push(target);
+                        if prev.and_then(stored_constant) == Some(end as isize) {
+                            calls.insert(pc, target);
+                            functions.insert(target);
+                            block_starts.insert(end);
+                            to_visit.push(end);
+                        }
+                        break;
+                    },
+                    Flow::Jump(Addr { mode: Mode::Relative, .. }) => {
+                        returns.insert(pc);
+                        break;
+                    },
+                    // Somewhere computed
+                    Flow::Jump(_) => break,
+                    Flow::Branch(addr) => {
+                        if let Addr { arg: target, mode: Mode::Immediate } = addr {
+                            if target >= 0 {
+                                jump_targets.insert(target as usize);
+                                block_starts.insert(target as usize);
+                                to_visit.push(target as usize);
+                            }
+                        }
+                        block_starts.insert(end);
+                    },
+                }
+                prev = Some(instruction);
+            }
+        }
+
+        let mut blocks: Vec<Block> = vec![];
+        for (&pc, &instruction) in &instructions {
+            let continues = blocks.last().is_some_and(|block| block.end() == pc && !block_starts.contains(&pc));
+            let ends_flow = blocks.last()
+                .and_then(|block| block.instructions.last())
+                .is_some_and(|&(_, last)| !matches!(Flow::of(last), Flow::Next | Flow::Branch(_)));
+            if continues && !ends_flow {
+                blocks.last_mut().unwrap().instructions.push((pc, instruction));
+            } else {
+                blocks.push(Block { start: pc, instructions: vec![(pc, instruction)], successors: vec![] });
+            }
+        }
+        for block in &mut blocks {
+            let (pc, last) = *block.instructions.last().unwrap();
+            let end = block.end();
+            let target = |addr: Addr| (addr.mode == Mode::Immediate && addr.arg >= 0).then_some(addr.arg as usize);
+            block.successors = match Flow::of(last) {
+                Flow::Next => vec![end],
+                Flow::Halt => vec![],
+                Flow::Jump(addr) if calls.contains_key(&pc) => target(addr).into_iter().chain([end]).collect(),
+                Flow::Jump(addr) => target(addr).into_iter().collect(),
+                Flow::Branch(addr) => target(addr).into_iter().chain([end]).collect(),
+            };
+            block.successors.retain(|next| instructions.contains_key(next));
+        }
+
+        let mut data: Vec<Range<usize>> = vec![];
+        for (addr, _) in is_code.iter().enumerate().filter(|(_, &code)| !code) {
+            match data.last_mut() {
+                Some(range) if range.end == addr => range.end += 1,
+                _ => data.push(addr..addr + 1),
+            }
+        }
+
+        Self {
+            program: program.to_vec(),
+            entry_points: entry_points.to_vec(),
+            blocks,
+            jump_targets,
+            functions,
+            data,
+            calls,
+            returns,
+        }
+    }
+
+    /// What a listing calls an address, if it's been given a name
+    pub fn label(&self, addr: usize) -> Option<String> {
+        if self.functions.contains(&addr) {
+            Some(format!("fn_{addr:04x}"))
+        } else if self.entry_points.contains(&addr) {
+            Some(format!("entry_{addr:04x}"))
+        } else if self.jump_targets.contains(&addr) {
+            Some(format!("L_{addr:04x}"))
+        } else {
+            None
+        }
+    }
+}
+
+/// A listing of the code and data in address order, with labels for jump
+/// targets and comments on calls and returns
+impl Display for Disassembly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut blocks = self.blocks.iter().peekable();
+        let mut data = self.data.iter().peekable();
+        loop {
+            let block_first = match (blocks.peek(), data.peek()) {
+                (Some(block), Some(range)) => block.start < range.start,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => break,
+            };
+            if block_first {
+                let block = blocks.next().unwrap();
+                if let Some(label) = self.label(block.start) {
+                    writeln!(f, "\n{label}:")?;
+                }
+                for &(pc, instruction) in &block.instructions {
+                    write!(f, "{pc:04x}    {instruction}")?;
+                    if let Some(&target) = self.calls.get(&pc) {
+                        write!(f, "    ; call {}", self.label(target).unwrap())?;
+                    } else if self.returns.contains(&pc) {
+                        write!(f, "    ; return")?;
+                    } else if let Flow::Jump(Addr { arg, mode: Mode::Immediate }) | Flow::Branch(Addr { arg, mode: Mode::Immediate }) = Flow::of(instruction) {
+                        if let Some(label) = usize::try_from(arg).ok().and_then(|target| self.label(target)) {
+                            write!(f, "    ; {label}")?;
+                        }
+                    }
+                    writeln!(f)?;
+                }
+            } else {
+                let range = data.next().unwrap();
+                for start in range.clone().step_by(8) {
+                    let values: Vec<String> = self.program[start..range.end.min(start + 8)]
+                        .iter()
+                        .map(|v| v.to_string())
+                        .collect();
+                    writeln!(f, "{start:04x}    data {}", values.join(", "))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_disassembly() {
+    // Calls a function that outputs [20], then halts
+    let program = [109, 100, 21101, 9, 0, 0, 1105, 1, 10, 99, 4, 20, 2106, 0, 0, 1, 2, 3, 4, 5, 42];
+    let disassembly = Disassembly::new(&program, &[0]);
+
+    let blocks: Vec<_> = disassembly.blocks.iter()
+        .map(|block| (block.start, block.end(), block.successors.clone()))
+        .collect();
+    assert_eq!(blocks, [(0, 9, vec![10, 9]), (9, 10, vec![]), (10, 15, vec![])]);
+    assert_eq!(disassembly.functions, BTreeSet::from([10]));
+    assert_eq!(disassembly.jump_targets, BTreeSet::from([10]));
+    assert_eq!(disassembly.data, vec![15..21]);
+    assert_eq!(disassembly.label(0).as_deref(), Some("entry_0000"));
+    assert_eq!(disassembly.label(9), None);
+
+    let listing = [
+        "",
+        "entry_0000:",
+        "0000    Rel +0x0064, ",
+        "0002    Str +0x0009, [$rel]",
+        "0006    Jmp +0x000a    ; call fn_000a",
+        "0009    Hlt ",
+        "",
+        "fn_000a:",
+        "000a    Out [+0x0014], ",
+        "000c    Jmp [$rel]    ; return",
+        "000f    data 1, 2, 3, 4, 5, 42",
+        "",
+    ];
+    assert_eq!(disassembly.to_string(), listing.join("\n"));
+
+    // Branches split blocks, and their targets get labels
+    let program = [3, 10, 1005, 10, 7, 99, 0, 104, 1, 99, 0];
+    let disassembly = Disassembly::new(&program, &[0]);
+    let starts: Vec<_> = disassembly.blocks.iter().map(|block| block.start).collect();
+    assert_eq!(starts, [0, 5, 7]);
+    assert_eq!(disassembly.blocks[0].successors, [7, 5]);
+    assert_eq!(disassembly.data, [6..7, 10..11]);
+    assert!(disassembly.to_string().contains("0002    Jit [+0x000a], +0x0007,     ; L_0007\n"));
+}
@@ -0,0 +1,96 @@
+use std::fmt::Display;
+
+/// Why an instruction couldn't run. Each carries the address of the
+/// instruction and its raw value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntcodeError {
+    UnknownOpcode { pc: usize, instruction: isize },
+    BadMode { pc: usize, instruction: isize, mode: isize },
+    WriteToImmediate { pc: usize, instruction: isize },
+    /// A negative address, or one beyond `Memory::MAX_LEN`
+    OutOfBounds { pc: usize, instruction: isize, addr: isize },
+    /// A jump to a negative address
+    NegativePointer { pc: usize, instruction: isize, target: isize },
+    /// An `Inp` run with nothing to read
+    NoInput { pc: usize, instruction: isize },
+}
+
+impl IntcodeError {
+    pub fn pc(&self) -> usize {
+        match *self {
+            | Self::UnknownOpcode { pc, .. }
+            | Self::BadMode { pc, .. }
+            | Self::WriteToImmediate { pc, .. }
+            | Self::OutOfBounds { pc, .. }
+            | Self::NegativePointer { pc, .. }
+            | Self::NoInput { pc, .. } => pc,
+        }
+    }
+    pub fn instruction(&self) -> isize {
+        match *self {
+            | Self::UnknownOpcode { instruction, .. }
+            | Self::BadMode { instruction, .. }
+            | Self::WriteToImmediate { instruction, .. }
+            | Self::OutOfBounds { instruction, .. }
+            | Self::NegativePointer { instruction, .. }
+            | Self::NoInput { instruction, .. } => instruction,
+        }
+    }
+}
+
+impl Display for IntcodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::UnknownOpcode { .. } => write!(f, "Unknown opcode")?,
+            Self::BadMode { mode, .. } => write!(f, "Bad parameter mode {mode}")?,
+            Self::WriteToImmediate { .. } => write!(f, "Write to an immediate operand")?,
+            Self::OutOfBounds { addr, .. } => write!(f, "Address {addr} out of bounds")?,
+            Self::NegativePointer { target, .. } => write!(f, "Jump to negative address {target}")?,
+            Self::NoInput { .. } => write!(f, "No input to read")?,
+        }
+        write!(f, " in {} at pc 0x{:04x}", self.instruction(), self.pc())
+    }
+}
+
+impl std::error::Error for IntcodeError {}
+
+#[test]
+fn test_errors() {
+    use crate::machine::{Machine, RunState};
+
+    let cases = [
+        (vec![1, 0, 0, 0, 42], IntcodeError::UnknownOpcode { pc: 4, instruction: 42 }),
+        (vec![301, 0, 0, 0], IntcodeError::BadMode { pc: 0, instruction: 301, mode: 3 }),
+        (vec![10001, 0, 0, 0], IntcodeError::WriteToImmediate { pc: 0, instruction: 10001 }),
+        (vec![1, -1, 0, 0], IntcodeError::OutOfBounds { pc: 0, instruction: 1, addr: -1 }),
+        (vec![1, 1 << 30, 0, 0], IntcodeError::OutOfBounds { pc: 0, instruction: 1, addr: 1 << 30 }),
+        (vec![1105, 1, -7], IntcodeError::NegativePointer { pc: 0, instruction: 1105, target: -7 }),
+        // Running off the end reads zeros
+        (vec![1101, 0, 0, 9], IntcodeError::UnknownOpcode { pc: 4, instruction: 0 }),
+    ];
+    for (program, error) in cases {
+        let mut machine = Machine::new(program, vec![]);
+        while machine.pc != error.pc() {
+            assert_eq!(machine.step(), None);
+        }
+        let before = machine.clone();
+        assert_eq!(machine.step(), Some(RunState::Error(error)));
+        assert_eq!(machine, before);
+        assert_eq!(machine.memory.len(), before.memory.len());
+        assert_eq!(machine.memory.get(error.pc()), error.instruction());
+    }
+    assert_eq!(
+        IntcodeError::BadMode { pc: 480, instruction: 301, mode: 3 }.to_string(),
+        "Bad parameter mode 3 in 301 at pc 0x01e0",
+    );
+
+    // `step` waits for input, but running the instruction regardless is an error
+    let mut machine = Machine::new(vec![203, 5, 99], vec![]);
+    assert_eq!(machine.step(), Some(RunState::NeedsInput));
+    let before = machine.clone();
+    let instruction = machine.decode().unwrap();
+    let error = machine.exec(instruction).unwrap_err();
+    assert_eq!(error, IntcodeError::NoInput { pc: 0, instruction: 203 });
+    assert_eq!((error.pc(), error.instruction()), (0, 203));
+    assert_eq!(machine, before);
+}
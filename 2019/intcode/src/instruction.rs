@@ -29,34 +29,53 @@ macro_rules! opcode_def {
         }
 
         impl $enum_name {
-            pub fn parse(machine: &$crate::machine::Machine, data: &[isize]) -> Option<Self> {
-                if machine.pc >= data.len() { return None }
-                let opcode = data[machine.pc];
-                let mode_0 = $crate::instruction::Mode::from_int(opcode / 100 % 10);
-                let mode_1 = $crate::instruction::Mode::from_int(opcode / 1000 % 10);
-                let mode_2 = $crate::instruction::Mode::from_int(opcode / 10000 % 10);
-                let modes = [mode_0, mode_1, mode_2];
+            /// Every opcode by name
+            pub const OPCODES: &[(&str, isize)] = &[$((stringify!($opcode), $opcode_int),)+];
+
+            pub fn parse(machine: &$crate::machine::Machine) -> Result<Self, $crate::error::IntcodeError> {
+                let pc = machine.pc;
+                let opcode = machine.memory.get(pc);
+                #[allow(unused)]
+                let mode = |idx: u32| {
+                    let mode = opcode / 10_isize.pow(idx + 2) % 10;
+                    $crate::instruction::Mode::from_int(mode)
+                        .ok_or($crate::error::IntcodeError::BadMode { pc, instruction: opcode, mode })
+                };
                 // println!("{opcode}");
                 match opcode % 100 {
                     $($opcode_int => {
                         #[allow(unused_mut, unused_variables)]
                         let mut idx = 0;
                         $(
-                            let $arg = Addr { arg: data[machine.pc+idx+1], mode: modes[idx] };
+                            let $arg = Addr { arg: machine.memory.get(pc+idx as usize+1), mode: mode(idx)? };
 
                             #[allow(unused_assignments)]
                             { idx += 1 }
                         )*
-                        Some(Self::$opcode {
+                        Ok(Self::$opcode {
                             $($arg,)*
                         })
                     },)+
-                    _ => None,
+                    _ => Err($crate::error::IntcodeError::UnknownOpcode { pc, instruction: opcode }),
                 }
 
             }
 
-            pub fn exec(&self, machine: &mut $crate::machine::Machine, data: &mut [isize]) {
+            /// Leaves the machine as it was if the instruction fails
+            pub fn exec(&self, machine: &mut $crate::machine::Machine) -> Result<(), $crate::error::IntcodeError> {
+                self.check(machine)?;
+                // Taken out while the instruction runs, so it can be borrowed alongside the machine
+                let mut memory = std::mem::take(&mut machine.memory);
+                let result = self.exec_with(machine, &mut memory);
+                machine.memory = memory;
+                result
+            }
+
+            fn exec_with(
+                &self,
+                machine: &mut $crate::machine::Machine,
+                data: &mut $crate::memory::Memory,
+            ) -> Result<(), $crate::error::IntcodeError> {
                 match *self {
                     $(Self::$opcode { $($arg,)* } => {
                         #[allow(unused_variables)]
@@ -78,6 +97,7 @@ macro_rules! opcode_def {
                         )?
                     })+
                 }
+                Ok(())
             }
 
             pub fn size(&self) -> usize {
@@ -101,16 +121,14 @@ macro_rules! opcode_def {
     };
 
     (@impl arg in $arg:ident <$machine:ident, $data:ident>) => {
-        let $arg = {
-            if $machine.debug {
-                println!("got {}: {}", stringify!($arg), $arg.get($data, $machine));
-            }
-            $arg.get($data, $machine)
-        };
+        let $arg = $arg.get($data, $machine)?;
+        if $machine.debug {
+            println!("got {}: {}", stringify!($arg), $arg);
+        }
     };
     (@impl arg out $arg:ident <$machine:ident, $data:ident>) => {
-        let mut $arg = $arg.set_fn($machine);
-        let mut $arg = move |val| $arg(val, $data);
+        let $arg = $arg.target($data, $machine)?;
+        let mut $arg = move |val| $data.set($arg, val);
     };
     (@impl arg raw $arg:ident <$machine:ident, $data:ident>) => {};
 }
@@ -119,36 +137,38 @@ use std::fmt::Debug;
 
 pub (crate) use opcode_def;
 
+use crate::error::IntcodeError;
 use crate::machine::Machine;
+use crate::memory::Memory;
 
 opcode_def!(
     pub enum Instruction {
         Add [1] { in a, in b, out to } => <>{ to(a + b) },
         Mul [2] { in a, in b, out to } => <>{ to(a * b) },
         Inp [3] { out to } => <machine> {
-            let input = machine.input();
+            let input = machine.input().expect("Inp is checked for input before it runs");
             if machine.debug {
                 println!("char: {input} ({})", input as u8 as char);
             }
             to(input)
         },
         Out [4] { in val } => <machine> { machine.output(val) },
-        Jit [5] { in cond, in addr } => custom<machine, _data> {
+        Jit [5] { in cond, in addr } => custom<machine, data> {
             if machine.debug {
                 println!("tru: cond: {cond}, addr: {addr}");
             }
             if cond != 0 {
-                machine.pc = addr as usize;
+                machine.pc = jump_target(addr, data, machine)?;
             } else {
                 machine.pc += 3;
             }
         },
-        Jif [6] { in cond, in addr } => custom<machine, _data> {
+        Jif [6] { in cond, in addr } => custom<machine, data> {
             if machine.debug {
                 println!("fls: cond: {cond}, addr: {addr}");
             }
             if cond == 0 {
-                machine.pc = addr as usize;
+                machine.pc = jump_target(addr, data, machine)?;
             } else {
                 machine.pc += 3;
             }
@@ -163,6 +183,18 @@ opcode_def!(
     }
 );
 
+impl Instruction {
+    /// Faults that can be caught before anything runs
+    fn check(&self, machine: &Machine) -> Result<(), IntcodeError> {
+        match self {
+            Self::Inp { .. } if machine.input_is_empty() => {
+                Err(IntcodeError::NoInput { pc: machine.pc, instruction: machine.memory.get(machine.pc) })
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
 impl std::fmt::Display for Instruction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -192,46 +224,58 @@ pub enum Mode {
     Relative
 }
 impl Mode {
-    fn from_int(int: isize) -> Self {
+    fn from_int(int: isize) -> Option<Self> {
         match int {
-            0 => Self::Position,
-            1 => Self::Immediate,
-            2 => Self::Relative,
-            _ => panic!("Invalid integer"),
+            0 => Some(Self::Position),
+            1 => Some(Self::Immediate),
+            2 => Some(Self::Relative),
+            _ => None,
         }
     }
-    pub fn get(&self, arg: isize, data: &[isize], offset: isize) -> isize {
+    /// The address an operand refers to, or `None` if it's immediate
+    pub fn address(&self, arg: isize, offset: isize) -> Option<isize> {
         // println!("{arg} ?+ {offset}");
         match self {
-            Self::Position => data[arg as usize],
-            Self::Immediate => arg,
-            Self::Relative => data[(arg + offset) as usize],
-        }
-    }
-    pub fn set(&self, arg: isize, val: isize, data: &mut [isize], offset: isize) {
-        match self {
-            Self::Position => data[arg as usize] = val,
-            Self::Immediate => unimplemented!("Cannot set an immediate value"),
-            Self::Relative => data[(arg + offset) as usize] = val,
+            Self::Position => Some(arg),
+            Self::Immediate => None,
+            Self::Relative => Some(arg + offset),
         }
     }
 }
 
+/// Errors refer to the instruction at `machine.pc`, which is in `data` while it runs
+fn checked_address(addr: isize, data: &Memory, machine: &Machine) -> Result<usize, IntcodeError> {
+    usize::try_from(addr)
+        .ok()
+        .filter(|&addr| addr < Memory::MAX_LEN)
+        .ok_or(IntcodeError::OutOfBounds { pc: machine.pc, instruction: data.get(machine.pc), addr })
+}
+fn jump_target(target: isize, data: &Memory, machine: &Machine) -> Result<usize, IntcodeError> {
+    usize::try_from(target)
+        .map_err(|_| IntcodeError::NegativePointer { pc: machine.pc, instruction: data.get(machine.pc), target })
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Addr { pub arg: isize, pub mode: Mode }
 
 impl Addr {
-    pub fn get(&self, data: &[isize], machine: &Machine) -> isize {
-        self.mode.get(self.arg, data, machine.offset)
+    pub fn get(&self, data: &Memory, machine: &Machine) -> Result<isize, IntcodeError> {
+        match self.mode.address(self.arg, machine.offset) {
+            Some(addr) => Ok(data.get(checked_address(addr, data, machine)?)),
+            None => Ok(self.arg),
+        }
     }
-    pub fn set(&self, val: isize, data: &mut [isize], machine: &Machine) {
-        self.mode.set(self.arg, val, data, machine.offset)
+    /// Where writing to this operand would go
+    pub fn target(&self, data: &Memory, machine: &Machine) -> Result<usize, IntcodeError> {
+        match self.mode.address(self.arg, machine.offset) {
+            Some(addr) => checked_address(addr, data, machine),
+            None => Err(IntcodeError::WriteToImmediate { pc: machine.pc, instruction: data.get(machine.pc) }),
+        }
     }
-
-    pub fn set_fn(&self, machine: &Machine) -> impl FnMut(isize, &mut [isize]) {
-        let Self { arg, mode } = *self;
-        let offset = machine.offset;
-        move |val, data| mode.set(arg as isize, val, data, offset)
+    pub fn set(&self, val: isize, data: &mut Memory, machine: &Machine) -> Result<(), IntcodeError> {
+        let target = self.target(data, machine)?;
+        data.set(target, val);
+        Ok(())
     }
 }
 
@@ -1,10 +1,11 @@
-use std::sync::mpsc::TryRecvError;
-
+use crate::error::IntcodeError;
 use crate::instruction::Instruction;
+use crate::memory::Memory;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Machine {
     pub pc: usize,
+    pub memory: Memory,
     pub input: (Vec<isize>, usize),
     pub output: Vec<isize>,
     pub halt: bool,
@@ -12,11 +13,23 @@ pub struct Machine {
     pub debug: bool,
 }
 
+/// Why a machine stopped running
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RunState {
+    /// The next instruction reads input, but there is none. Feed some and run again.
+    NeedsInput,
+    Output(isize),
+    Halted,
+    /// The next instruction couldn't run. The machine is left as it was.
+    Error(IntcodeError),
+}
+
 
 impl Machine {
-    pub fn new(input: Vec<isize>) -> Self {
+    pub fn new(program: impl Into<Memory>, input: Vec<isize>) -> Self {
         Self {
             pc: 0,
+            memory: program.into(),
             input: (input, 0),
             output: vec![],
             halt: false,
@@ -24,17 +37,18 @@ impl Machine {
             debug: false,
         }
     }
-    pub fn new_ascii(input: &str) -> Self {
+    pub fn new_ascii(program: impl Into<Memory>, input: &str) -> Self {
         Self::new(
+            program,
             input.chars().map(|c| c as u8 as isize).collect()
         )
     }
     pub fn input_is_empty(&self) -> bool {
         self.input.1 >= self.input.0.len()
     }
-    pub fn input(&mut self) -> isize {
+    pub fn input(&mut self) -> Option<isize> {
         if self.input.1 >= self.input.0.len() {
-            -1
+            None
         } else {
             let output = self.input.0[self.input.1];
             self.input.1 += 1;
@@ -42,34 +56,102 @@ impl Machine {
                 self.input.0.drain(0..self.input.1).count();
                 self.input.1 = 0;
             }
-            output
+            Some(output)
         }
     }
     pub fn output(&mut self, val: isize) {
         self.output.push(val);
     }
 
-    pub fn decode(&self, data: &[isize]) -> Option<Instruction> {
-        Instruction::parse(self, data)
+    pub fn feed(&mut self, input: impl IntoIterator<Item = isize>) {
+        self.input.0.extend(input);
+    }
+    pub fn feed_ascii(&mut self, input: &str) {
+        self.feed(input.bytes().map(|b| b as isize));
+    }
+    /// Takes everything output so far
+    pub fn drain_output(&mut self) -> std::vec::Drain<'_, isize> {
+        self.output.drain(..)
+    }
+    /// Takes everything output so far, as text
+    pub fn drain_ascii(&mut self) -> String {
+        self.drain_output().map(|v| v as u8 as char).collect()
+    }
+
+    pub fn decode(&self) -> Result<Instruction, IntcodeError> {
+        Instruction::parse(self)
     }
-    pub fn exec(&mut self, instruction: Instruction, data: &mut [isize]) {
-        instruction.exec(self, data);
+    pub fn exec(&mut self, instruction: Instruction) -> Result<(), IntcodeError> {
+        instruction.exec(self)
     }
 
-    pub fn step(&mut self, data: &mut [isize]) -> Result<(), ()> {
-        if self.halt { return Err(()) }
+    /// Runs one instruction. `None` if it had nothing to report. Outputs are
+    /// also added to `output`.
+    pub fn step(&mut self) -> Option<RunState> {
+        if self.halt { return Some(RunState::Halted) }
 
-        if let Some(instruction) = self.decode(data) {
-            self.exec(instruction, data);
-            Ok(())
-        } else {
-            Err(())
+        let instruction = match self.decode() {
+            Ok(instruction) => instruction,
+            Err(e) => return Some(RunState::Error(e)),
+        };
+        if matches!(instruction, Instruction::Inp { .. }) && self.input_is_empty() {
+            return Some(RunState::NeedsInput);
+        }
+        if let Err(e) = self.exec(instruction) {
+            return Some(RunState::Error(e));
+        }
+        match instruction {
+            Instruction::Out { .. } => self.output.last().map(|&val| RunState::Output(val)),
+            Instruction::Hlt { .. } => Some(RunState::Halted),
+            _ => None,
+        }
+    }
+
+    /// Runs until there's something to report. An output is taken back off
+    /// `output`, so only the `RunState` has it.
+    pub fn run_until_event(&mut self) -> RunState {
+        loop {
+            let Some(state) = self.step() else { continue };
+            if let RunState::Output(_) = state {
+                self.output.pop();
+            }
+            return state;
+        }
+    }
+    /// The next output, or why there wasn't one
+    pub fn run_until_output(&mut self) -> Result<isize, RunState> {
+        match self.run_until_event() {
+            RunState::Output(val) => Ok(val),
+            state => Err(state),
+        }
+    }
+    /// Runs until it's waiting on input, collecting outputs in `output`.
+    /// Fails if it halts or errors first.
+    pub fn run_until_input_needed(&mut self) -> Result<(), RunState> {
+        loop {
+            match self.step() {
+                None | Some(RunState::Output(_)) => (),
+                Some(RunState::NeedsInput) => return Ok(()),
+                Some(state) => return Err(state),
+            }
+        }
+    }
+    /// Runs until it halts, collecting outputs in `output`. Fails if it runs out
+    /// of input or errors first.
+    pub fn run_until_halt(&mut self) -> Result<(), RunState> {
+        loop {
+            match self.step() {
+                None | Some(RunState::Output(_)) => (),
+                Some(RunState::Halted) => return Ok(()),
+                Some(state) => return Err(state),
+            }
         }
     }
 
+    /// Runs interactively, with input and output as ASCII bytes. Waits for
+    /// input whenever the program needs it.
     pub fn run(
         &mut self,
-        data: &mut [isize],
         mut input: impl std::io::Read + Send,
         mut output: impl std::io::Write,
     ) -> std::io::Result<()> {
@@ -84,7 +166,8 @@ impl Machine {
                                 Ok(()) => (),
                                 Err(_) => break,
                             },
-                            Ok(0) => (),
+                            // End of input
+                            Ok(0) => break,
                             Ok(_) => unreachable!("Read more than 1 byte into a 1 byte buffer"),
                             Err(e) => {
                                 sender.send(Err(e)).unwrap();
@@ -93,33 +176,25 @@ impl Machine {
                     }
                 });
 
-                while let Some(instruction) = Instruction::parse(&self, &data) {
-                    instruction.exec(self, data);
-                    
-                    while let Some(&ch) = self.output.first() {
-                        self.output.remove(0);
-                        output.write(&[ch as u8])?;
-                        output.flush()?;
-                    }
-        
-                    // Read in
-                    loop {
-                        match receiver.try_recv() {
+                loop {
+                    match self.run_until_event() {
+                        RunState::Output(ch) => {
+                            output.write_all(&[ch as u8])?;
+                            output.flush()?;
+                        },
+                        RunState::NeedsInput => match receiver.recv() {
                             Ok(Ok(byte)) => self.input.0.push(byte as isize),
                             Ok(Err(e)) => {
                                 reader_handle.join().unwrap();
                                 return Err(e);
                             },
-                            Err(TryRecvError::Empty) => break,
-                            Err(TryRecvError::Disconnected) => {
-                                reader_handle.join().unwrap();
-                                return Err(std::io::Error::last_os_error());
-                            }
-                        }
+                            // The input ran out while the program still wanted more
+                            Err(_) => return Ok(()),
+                        },
+                        RunState::Halted => return Ok(()),
+                        RunState::Error(e) => return Err(std::io::Error::other(e)),
                     }
                 }
-
-                Ok(())
             }
         )
     }
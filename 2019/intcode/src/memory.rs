@@ -0,0 +1,107 @@
+use std::hash::{Hash, Hasher};
+use std::ops::{Index, IndexMut};
+
+/// A machine's memory: the program, then zeros forever. Grows when written past
+/// the end, so programs never need padding.
+#[derive(Debug, Clone, Default)]
+pub struct Memory {
+    cells: Vec<isize>,
+}
+
+impl Memory {
+    /// Addresses this far out are almost certainly a bug, and growing to them
+    /// would take over 100MB
+    pub const MAX_LEN: usize = 1 << 24;
+
+    pub fn new(program: Vec<isize>) -> Self {
+        Self { cells: program }
+    }
+
+    pub fn get(&self, addr: usize) -> isize {
+        self.cells.get(addr).copied().unwrap_or(0)
+    }
+    pub fn set(&mut self, addr: usize, val: isize) {
+        self[addr] = val;
+    }
+
+    /// Up to the last address written or loaded, whichever is later
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+    pub fn as_slice(&self) -> &[isize] {
+        &self.cells
+    }
+
+    /// Without the zeros at the end, which are there either way
+    fn used(&self) -> &[isize] {
+        let len = self.cells.iter().rposition(|&cell| cell != 0).map_or(0, |last| last + 1);
+        &self.cells[..len]
+    }
+}
+
+impl From<Vec<isize>> for Memory {
+    fn from(program: Vec<isize>) -> Self {
+        Self::new(program)
+    }
+}
+impl From<&[isize]> for Memory {
+    fn from(program: &[isize]) -> Self {
+        Self::new(program.to_vec())
+    }
+}
+
+impl Index<usize> for Memory {
+    type Output = isize;
+    fn index(&self, addr: usize) -> &isize {
+        self.cells.get(addr).unwrap_or(&0)
+    }
+}
+impl IndexMut<usize> for Memory {
+    fn index_mut(&mut self, addr: usize) -> &mut isize {
+        if addr >= self.cells.len() {
+            self.cells.resize(addr + 1, 0);
+        }
+        &mut self.cells[addr]
+    }
+}
+
+/// Memories are equal if they hold the same values, however far each has grown
+impl PartialEq for Memory {
+    fn eq(&self, other: &Self) -> bool {
+        self.used() == other.used()
+    }
+}
+impl Eq for Memory {}
+impl Hash for Memory {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.used().hash(state);
+    }
+}
+
+#[test]
+fn test_memory() {
+    use std::hash::BuildHasher;
+
+    let mut memory = Memory::from(&[1, 2, 3][..]);
+    assert_eq!((memory.get(2), memory.get(3), memory[1000]), (3, 0, 0));
+    assert_eq!(memory.len(), 3);
+
+    memory.set(9, 7);
+    assert_eq!(memory.len(), 10);
+    assert_eq!(memory.as_slice(), &[1, 2, 3, 0, 0, 0, 0, 0, 0, 7]);
+    memory[12] += 1;
+    assert_eq!((memory.len(), memory[12]), (13, 1));
+
+    // Trailing zeros don't count, however they got there
+    let short = Memory::new(vec![1, 2, 3]);
+    let mut grown = short.clone();
+    grown[100] = 0;
+    assert_eq!(short, grown);
+    let state = std::collections::hash_map::RandomState::new();
+    assert_eq!(state.hash_one(&short), state.hash_one(&grown));
+    assert_eq!(Memory::new(vec![0, 0]), Memory::default());
+    assert_ne!(short, Memory::new(vec![1, 2, 3, 0, 1]));
+}
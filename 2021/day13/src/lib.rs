@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use aoc_tools::ocr::PixelGrid;
+
 aoc_tools::aoc_sol!(day13 2021: part1, part2);
 aoc_tools::pos!(i16; +y => D);
 
@@ -87,14 +89,12 @@ pub fn part1(input: &str) -> usize {
     transparency.dots.len()
 }
 
-pub fn part2(input: &str) -> String {
+pub fn part2(input: &str) -> PixelGrid {
     let (mut transparency, folds) = parse_input(input);
     for fold in folds {
         transparency.fold(fold);
     }
-    // TODO: Decode it into a `String`
-    println!("{transparency:?}");
-    "".to_string()
+    PixelGrid::from_points(transparency.dots.iter().map(|dot| (dot.x, dot.y)))
 }
 
 fn parse_input(input: &str) -> (Transparency, Vec<Fold>) {
@@ -80,6 +80,11 @@ impl FromStr for Digit {
 
 #[derive(Clone, PartialEq, Eq)]
 pub struct Number(SmallVec<MAX_DIGITS, Digit>);
+impl From<Number> for aoc_tools::answers::Answer {
+    fn from(value: Number) -> Self {
+        Self::Str(value.to_string())
+    }
+}
 impl Display for Number {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         for &digit in self.0.iter() {
@@ -1,3 +1,5 @@
+use aoc_tools::grid::Grid;
+
 aoc_tools::aoc_sol!(day10 2024: part1, part2);
 type Scalar = i16;
 aoc_tools::pos!(Scalar; +y=>D);
@@ -49,65 +51,32 @@ fn count_9s_paths(starts: impl Iterator<Item = Pos>, topography: &Topography) ->
 }
 
 fn parse_input(input: &str) -> Topography {
-    let levels = input.lines()
-        .filter(|l| !l.trim().is_empty())
-        .map(|v| v.chars().map(|v| v as u8 - b'0'))
-        .map(|r| r.collect::<Vec<_>>())
-        .collect();
+    let levels = Grid::parse(input, |c| c as u8 - b'0');
 
     Topography { levels }
 }
 
 #[derive(Clone)]
 struct Topography {
-    levels: Vec<Vec<u8>>,
+    levels: Grid<u8>,
 }
 impl Topography {
-    pub fn width(&self) -> usize { self.levels[0].len() }
-    pub fn height(&self) -> usize { self.levels.len() }
     pub fn get(&self, pos: Pos) -> u8 {
-        if 0 > pos.x || pos.x >= self.width() as Scalar {
-            255
-        } else if 0 > pos.y || pos.y >= self.height() as Scalar {
-            255
-        } else {
-            self.levels[pos.y as usize][pos.x as usize]
-        }
+        self.levels.get(pos).copied().unwrap_or(255)
     }
     pub fn adjacent_iter(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
         let at = self.get(pos);
-        // let candidates = if at == 255 {
-        //     vec![]
-        // } else {
-        //     vec![pos.add(Pos::N), pos.add(Pos::S), pos.add(Pos::W), pos.add(Pos::E)]
-        // };
-        [pos.add(Pos::N), pos.add(Pos::S), pos.add(Pos::W), pos.add(Pos::E)]
-            .into_iter()
-            // .filter(move |_| at != 255)
-            .filter(move |v| self.get(*v) == at + 1)
-        // candidates.into_iter().filter(move |v| self.get(*v) == at + 1)
+        self.levels
+            .neighbors4(pos)
+            .filter(move |&v| self.levels[v] == at + 1)
     }
 
     pub fn trailheads(&self) -> impl Iterator<Item = Pos> + '_ {
-        self.levels.iter()
-            .enumerate()
-            .flat_map(|(y, row)| {
-                row.iter()
-                    .enumerate()
-                    .filter_map(move |(x, &cell)| {
-                        (cell == 0).then_some(Pos { x: x as Scalar, y: y as Scalar })
-                    })
-            })
+        self.levels.positions().filter(|(_, &cell)| cell == 0).map(|(pos, _)| pos)
     }
 }
 impl Debug for Topography {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in &self.levels {
-            for &cell in row {
-                write!(f, "{cell}")?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        write!(f, "{:?}", self.levels)
     }
 }
@@ -2,36 +2,48 @@
 
 type PosType = u8;
 
-aoc_tools::aoc_sol!(day14 2024: part1, part2);
+aoc_tools::aoc_sol!(day14 2024: part1, part2; params: Params; examples: small);
 aoc_tools::pos!(PosType);
 aoc_tools::fast_hash!();
+aoc_tools::params! {
+    width: PosType = 101, test 11;
+    height: PosType = 103, test 7;
+    part1_seconds: PosType = 100;
+}
 
-const PART_1_ITERS: PosType = 100;
-pub fn part1(input: &str) -> usize {
-    let mut robots = parse_input(input);
+impl Params {
+    fn field(&self) -> Pos {
+        Pos { x: self.width, y: self.height }
+    }
+}
+
+pub fn part1(input: &str, params: &Params) -> usize {
+    let field = params.field();
+    let mut robots = parse_input(input, field);
 
     robots.iter_mut().for_each(|robot| {
-        robot.step_n(PART_1_ITERS);
+        robot.step_n(params.part1_seconds, field);
     });
 
-    let quadrant_ul = robots.iter().filter(|r| r.quadrant() == (-1, -1)).count();
-    let quadrant_ur = robots.iter().filter(|r| r.quadrant() == (-1,  1)).count();
-    let quadrant_dl = robots.iter().filter(|r| r.quadrant() == ( 1, -1)).count();
-    let quadrant_dr = robots.iter().filter(|r| r.quadrant() == ( 1,  1)).count();
+    let quadrant_ul = robots.iter().filter(|r| r.quadrant(field) == (-1, -1)).count();
+    let quadrant_ur = robots.iter().filter(|r| r.quadrant(field) == (-1,  1)).count();
+    let quadrant_dl = robots.iter().filter(|r| r.quadrant(field) == ( 1, -1)).count();
+    let quadrant_dr = robots.iter().filter(|r| r.quadrant(field) == ( 1,  1)).count();
 
     quadrant_ul * quadrant_ur * quadrant_dl * quadrant_dr
 }
 
-pub fn part2(input: &str) -> usize {
-    let mut robots: Vec<_> = parse_input(input);
+pub fn part2(input: &str, params: &Params) -> usize {
+    let field = params.field();
+    let mut robots: Vec<_> = parse_input(input, field);
 
     let mut step = 0;
 
     let mut best_christmas_tree_val = u16::MAX;
     let mut best_christmas_tree_step = step;
 
-    while step < FIELD_MODULO.x as usize * FIELD_MODULO.y as usize {
-        let christmas_untreeness = untreeness_and_step(&mut robots);
+    while step < field.x as usize * field.y as usize {
+        let christmas_untreeness = untreeness_and_step(&mut robots, field);
         if christmas_untreeness < best_christmas_tree_val {
             best_christmas_tree_val = christmas_untreeness;
             best_christmas_tree_step = step;
@@ -43,7 +55,7 @@ pub fn part2(input: &str) -> usize {
 }
 
 #[inline(never)]
-fn parse_input(input: &str) -> Vec<Robot> {
+fn parse_input(input: &str, field: Pos) -> Vec<Robot> {
     fn parse_i16(s: &str) -> i16 {
         aoc_tools::parse_unsigned!(parse_u16<u16>(<= 8 digits));
         let (negate, s) = if s.as_bytes()[0] == b'-' {
@@ -69,7 +81,7 @@ fn parse_input(input: &str) -> Vec<Robot> {
 
             Robot {
                 pos: Pos { x: parse_i16(pos_x) as u8, y: parse_i16(pos_y) as u8 },
-                vel: Robot::fix_vel_positive(parse_i16(vel_x), parse_i16(vel_y)),
+                vel: Robot::fix_vel_positive(parse_i16(vel_x), parse_i16(vel_y), field),
             }
         })
         .for_each(|r| output.push(r));
@@ -85,40 +97,37 @@ struct Robot {
     vel: Pos,
 }
 
-// const FIELD_MODULO: Pos = Pos { x: 11, y: 7 };
-const FIELD_MODULO: Pos = Pos { x: 101, y: 103 };
-
 impl Robot {
-    pub fn step(&mut self) {
+    pub fn step(&mut self, field: Pos) {
         self.pos = self.pos.add(self.vel);
-        self.pos.x = self.pos.x - if self.pos.x >= FIELD_MODULO.x { FIELD_MODULO.x } else { 0 };
-        self.pos.y = self.pos.y - if self.pos.y >= FIELD_MODULO.y { FIELD_MODULO.y } else { 0 };
+        self.pos.x = self.pos.x - if self.pos.x >= field.x { field.x } else { 0 };
+        self.pos.y = self.pos.y - if self.pos.y >= field.y { field.y } else { 0 };
     }
-    pub fn step_n(&mut self, n: PosType) {
-        let coeff_x = n % FIELD_MODULO.x;
-        let coeff_y = n % FIELD_MODULO.y;
+    pub fn step_n(&mut self, n: PosType, field: Pos) {
+        let coeff_x = n % field.x;
+        let coeff_y = n % field.y;
 
         let new_pos_x = self.pos.x as u16 + self.vel.x as u16 * coeff_x as u16;
         let new_pos_y = self.pos.y as u16 + self.vel.y as u16 * coeff_y as u16;
         
-        let new_pos_x = new_pos_x % FIELD_MODULO.x as u16;
-        let new_pos_y = new_pos_y % FIELD_MODULO.y as u16;
+        let new_pos_x = new_pos_x % field.x as u16;
+        let new_pos_y = new_pos_y % field.y as u16;
         
         self.pos.x = new_pos_x as PosType;
         self.pos.y = new_pos_y as PosType;
     }
 
-    pub fn quadrant(&self) -> (i8, i8) {
-        let x = if self.pos.x < FIELD_MODULO.x / 2 {
+    pub fn quadrant(&self, field: Pos) -> (i8, i8) {
+        let x = if self.pos.x < field.x / 2 {
             -1
-        } else if self.pos.x == FIELD_MODULO.x / 2 {
+        } else if self.pos.x == field.x / 2 {
             0
         } else {
             1
         };
-        let y = if self.pos.y < FIELD_MODULO.y / 2 {
+        let y = if self.pos.y < field.y / 2 {
             -1
-        } else if self.pos.y == FIELD_MODULO.y / 2 {
+        } else if self.pos.y == field.y / 2 {
             0
         } else {
             1
@@ -127,16 +136,16 @@ impl Robot {
         (x, y)
     }
 
-    pub fn fix_vel_positive(x: i16, y: i16) -> Pos {
+    pub fn fix_vel_positive(x: i16, y: i16, field: Pos) -> Pos {
         Pos {
-            x: x.rem_euclid(FIELD_MODULO.x as i16) as PosType,
-            y: y.rem_euclid(FIELD_MODULO.y as i16) as PosType,
+            x: x.rem_euclid(field.x as i16) as PosType,
+            y: y.rem_euclid(field.y as i16) as PosType,
         }
     }
 }
 
 #[inline(never)]
-fn untreeness_and_step(robots: &mut [Robot]) -> u16 {
+fn untreeness_and_step(robots: &mut [Robot], field: Pos) -> u16 {
     assert!(!robots.is_empty());
 
     let mut sum_x: u16 = 0;
@@ -155,7 +164,7 @@ fn untreeness_and_step(robots: &mut [Robot]) -> u16 {
         let diff_x = (robot.pos.x as u16).abs_diff(mean_x);
         let diff_y = (robot.pos.y as u16).abs_diff(mean_y);
 
-        robot.step();
+        robot.step(field);
 
         n_mad += diff_x + diff_y;
     }
@@ -164,9 +173,9 @@ fn untreeness_and_step(robots: &mut [Robot]) -> u16 {
 }
 
 #[allow(dead_code)]
-fn print_state(robots: &[Robot]) {
-    for y in 0..FIELD_MODULO.y {
-        for x in 0..FIELD_MODULO.x {
+fn print_state(robots: &[Robot], field: Pos) {
+    for y in 0..field.y {
+        for x in 0..field.x {
             if robots.iter().any(|r| r.pos == Pos { x, y }) {
                 print!("#");
             } else {
@@ -1,40 +1,43 @@
-aoc_tools::aoc_sol!(day18 2024: part1, part2);
+aoc_tools::aoc_sol!(day18 2024: part1, part2; params: Params; examples: small);
 aoc_tools::pos!(isize; +y => D);
+aoc_tools::params! {
+    size: isize = 71, test 7;
+    part1_bytes: usize = 1024, test 12;
+}
 
-pub fn part1(input: &str) -> usize {
-    // const BYTES_TO_FALL_FIRST: usize = 12;
-    const BYTES_TO_FALL_FIRST: usize = 1024;
-
+pub fn part1(input: &str, params: &Params) -> usize {
+    let size = Pos { x: params.size, y: params.size };
     let bytes = parse_input(input);
-    let mut map = Map::new();
-    for i in 0..BYTES_TO_FALL_FIRST {
+    let mut map = Map::new(size);
+    for i in 0..params.part1_bytes {
         map.set(bytes[i], true);
     }
 
-    map.path(Pos { x: 0, y: 0 }, DIMENSIONS.sub(Pos { x: 1, y: 1 })).unwrap()
+    map.path(Pos { x: 0, y: 0 }, size.sub(Pos { x: 1, y: 1 })).unwrap()
 }
 
-pub fn part2(input: &str) -> String {
+pub fn part2(input: &str, params: &Params) -> String {
+    let size = Pos { x: params.size, y: params.size };
     let bytes = parse_input(input);
 
     let start_pos = Pos { x: 0, y: 0 };
-    let end_pos = DIMENSIONS.add(Pos::N).add(Pos::W);
+    let end_pos = size.add(Pos::N).add(Pos::W);
 
     let mut start = 0;
     let mut end = bytes.len();
     while start != end {
         let test = (start + end) / 2;
-        let map = Map::n_corrupted(&bytes, test);
+        let map = Map::n_corrupted(&bytes, test, size);
 
-        if map.path(Pos { x: 0, y: 0 }, DIMENSIONS.sub(Pos { x: 1, y: 1 })).is_some() {
+        if map.path(Pos { x: 0, y: 0 }, size.sub(Pos { x: 1, y: 1 })).is_some() {
             start = test + 1;
         } else {
             end = test;
         }
     }
 
-    assert!(Map::n_corrupted(&bytes, start-1).path(start_pos, end_pos).is_some());
-    assert!(Map::n_corrupted(&bytes, start).path(start_pos, end_pos).is_none());
+    assert!(Map::n_corrupted(&bytes, start-1, size).path(start_pos, end_pos).is_some());
+    assert!(Map::n_corrupted(&bytes, start, size).path(start_pos, end_pos).is_none());
 
     let blocking_byte = bytes[start-1];
 
@@ -51,30 +54,29 @@ fn parse_input(input: &str) -> Vec<Pos> {
         .collect()
 }
 
-// const DIMENSIONS: Pos = Pos { x: 7, y: 7 };
-const DIMENSIONS: Pos = Pos { x: 71, y: 71 };
-
 #[derive(Clone, PartialEq, Eq)]
 struct Map {
     corrupted: Vec<Vec<bool>>,
+    size: Pos,
 }
 impl Map {
-    pub fn new() -> Self {
+    pub fn new(size: Pos) -> Self {
         Self {
-            corrupted: vec![vec![false; DIMENSIONS.x as usize]; DIMENSIONS.y as usize],
+            corrupted: vec![vec![false; size.x as usize]; size.y as usize],
+            size,
         }
     }
     pub fn get(&self, pos: Pos) -> Option<bool> {
-        if !(0..DIMENSIONS.x).contains(&pos.x) { return None; }
-        if !(0..DIMENSIONS.y).contains(&pos.y) { return None; }
+        if !(0..self.size.x).contains(&pos.x) { return None; }
+        if !(0..self.size.y).contains(&pos.y) { return None; }
         Some(self.corrupted[pos.y as usize][pos.x as usize])
     }
     pub fn set(&mut self, pos: Pos, b: bool) {
         self.corrupted[pos.y as usize][pos.x as usize] = b;
     }
 
-    pub fn n_corrupted(bytes: &[Pos], n: usize) -> Self {
-        let mut map = Self::new();
+    pub fn n_corrupted(bytes: &[Pos], n: usize, size: Pos) -> Self {
+        let mut map = Self::new(size);
         for i in 0..n {
             map.set(bytes[i], true);
         }
@@ -115,8 +117,8 @@ impl Map {
 
 impl Debug for Map {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..DIMENSIONS.y {
-            for x in 0..DIMENSIONS.x {
+        for y in 0..self.size.y {
+            for x in 0..self.size.x {
                 let ch = match self.get(Pos { x, y }) {
                     Some(true) => '#',
                     Some(false) => '.',
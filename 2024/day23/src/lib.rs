@@ -1,3 +1,5 @@
+use aoc_tools::graph::{Graph, GraphAlgorithms};
+
 aoc_tools::aoc_sol!(day23 2024: part1, part2);
 
 pub fn part1(input: &str) -> usize {
@@ -24,33 +26,20 @@ pub fn part1(input: &str) -> usize {
 
 pub fn part2(input: &str) -> String {
     let connections = parse_input(input);
-    let mut groups: Vec<_> = connections.keys().map(|&c| vec![c]).collect();
-
-    let mut biggest_seen = vec![];
-    while let Some(group) = groups.pop() {
-        // Get non-group computers that all computers in the group have direct
-        // connections to
-        let mut new_possible_connections: HashSet<_> = connections.get(&group[0]).unwrap().clone();
-        for other_computer_in_group in group.iter().rev() {
-            let other_computer_connections = connections.get(other_computer_in_group).unwrap();
-            new_possible_connections.retain(|c| c > other_computer_in_group && other_computer_connections.contains(c));
-        }
-        // Optimization: If the biggest seen is larger than an expanded group
-        // will ever be, discard this expanded group
-        if new_possible_connections.len() + group.len() < biggest_seen.len() { continue; }
-
-        for new_connection in new_possible_connections {
-            let mut new_group = group.clone();
-            new_group.push(new_connection);
-
-            if biggest_seen.len() < new_group.len() {
-                biggest_seen = new_group.clone();
-            }
-            groups.push(new_group);
+    let mut graph = Graph::<Computer, (), ()>::new();
+    for &computer in connections.keys() {
+        graph.insert_node(computer, ());
+    }
+    for (a, a_conns) in &connections {
+        for b in a_conns.iter().filter(|b| *b > a) {
+            graph.insert_edge(a, b, ());
         }
     }
 
-    let mut output = biggest_seen
+    let mut party = graph.max_clique();
+    party.sort();
+
+    let mut output = party
         .into_iter().map(|c| format!("{c:?},"))
         .collect::<String>();
     output.pop();
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Discovers the workspace's day crates and generates the bench registrations:
+///
+/// - `separate_bench.rs`: a `day_bench!` per day plus `criterion_main!`
+/// - `benchYYYY.rs`: a `multi_day_bench!` for the whole year plus `criterion_main!`
+/// - `registry.rs`: which days are benchable, and why the others aren't
+fn main() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let manifest_dir = Path::new(&manifest_dir);
+    let workspace_dir = manifest_dir.parent().unwrap();
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let out_dir = Path::new(&out_dir);
+
+    let bench_manifest = aoc_workspace::read_manifest(&manifest_dir.join("Cargo.toml")).unwrap_or_else(|e| panic!("{e}"));
+    let days = aoc_workspace::day_crates(workspace_dir).unwrap_or_else(|e| panic!("{e}"));
+    let missing = aoc_workspace::missing_dependencies(&bench_manifest, &days);
+
+    // Every `src/benchYYYY.rs` needs a generated file, even if the year has no days
+    let mut benchable: BTreeMap<u16, Vec<u8>> = BTreeMap::new();
+    for entry in std::fs::read_dir(manifest_dir.join("src")).unwrap() {
+        let file_name = entry.unwrap().file_name();
+        let Some(file_name) = file_name.to_str() else { continue };
+        let Some(year) = file_name.strip_prefix("bench").and_then(|f| f.strip_suffix(".rs")) else { continue };
+        let Ok(year) = year.parse() else { continue };
+        benchable.insert(year, vec![]);
+    }
+    let mut unbenchable = vec![];
+    for day in &days {
+        benchable.entry(day.year).or_default();
+        println!("cargo:rerun-if-changed={}", workspace_dir.join(&day.path).join("src/lib.rs").display());
+
+        let reason = if let Err(reason) = &day.parts {
+            Some(reason.clone())
+        } else if missing.contains(&day) {
+            println!("cargo:warning={} isn't a dependency of bench yet, so it won't be benchmarked. Run `cargo aoc-workspace sync`.", day.name());
+            Some("not a dependency of bench (run `cargo aoc-workspace sync`)".to_string())
+        } else {
+            ["part1", "part2"].into_iter()
+                .find(|part| !day.has_part(part))
+                .map(|part| format!("`aoc_sol!` has no `{part}`"))
+        };
+
+        match reason {
+            Some(reason) => unbenchable.push((day.year, day.day, reason)),
+            None => benchable.get_mut(&day.year).unwrap().push(day.day),
+        }
+    }
+
+    let module = |year: u16, day: u8| format!("day{day:02}_{year}");
+
+    let mut separate = String::new();
+    for (&year, days) in benchable.iter().rev() {
+        for &day in days {
+            writeln!(separate, "aoc_tools::day_bench!(day{day:02} {year}: {});", module(year, day)).unwrap();
+        }
+    }
+    let groups: Vec<_> = benchable.iter()
+        .rev()
+        .flat_map(|(&year, days)| days.iter().map(move |&day| format!("{0}::{0}", module(year, day))))
+        .collect();
+    write_criterion_main(&mut separate, &groups);
+    std::fs::write(out_dir.join("separate_bench.rs"), separate).unwrap();
+
+    for (&year, days) in &benchable {
+        let mut year_bench = String::new();
+        if !days.is_empty() {
+            let days: Vec<_> = days.iter().map(|&day| format!("day{day:02} ({})", module(year, day))).collect();
+            writeln!(year_bench, "aoc_tools::multi_day_bench!(bench_{year} {year}: {});", days.join(", ")).unwrap();
+            write_criterion_main(&mut year_bench, &[format!("bench_{year}::bench_{year}")]);
+        } else {
+            write_criterion_main(&mut year_bench, &[]);
+        }
+        std::fs::write(out_dir.join(format!("bench{year}.rs")), year_bench).unwrap();
+    }
+
+    let mut registry = String::from("pub const BENCHABLE: &[(u16, u8)] = &[\n");
+    for (year, days) in &benchable {
+        for day in days {
+            writeln!(registry, "    ({year}, {day}),").unwrap();
+        }
+    }
+    registry.push_str("];\n\npub const UNBENCHABLE: &[(u16, u8, &str)] = &[\n");
+    for (year, day, reason) in unbenchable {
+        writeln!(registry, "    ({year}, {day}, {reason:?}),").unwrap();
+    }
+    registry.push_str("];\n");
+    std::fs::write(out_dir.join("registry.rs"), registry).unwrap();
+
+    println!("cargo:rerun-if-changed={}", workspace_dir.join("Cargo.toml").display());
+    println!("cargo:rerun-if-changed={}", manifest_dir.join("Cargo.toml").display());
+}
+
+fn write_criterion_main(output: &mut String, groups: &[String]) {
+    if groups.is_empty() {
+        output.push_str("fn main() {}\n");
+    } else {
+        writeln!(output, "criterion::criterion_main!({});", groups.join(", ")).unwrap();
+    }
+}
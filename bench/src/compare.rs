@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BenchId {
+    pub year: Option<u16>,
+    pub day: u8,
+    pub part: u8,
+}
+impl BenchId {
+    /// Parses the names given by `day_bench!`: `2024 day 06 Part 2` or `Day 06 Part 2`
+    pub fn from_bench_name(name: &str) -> Option<Self> {
+        let words: Vec<_> = name.split_whitespace().collect();
+        let (year, words) = match words.as_slice() {
+            [year, rest @ ..] if year.len() == 4 => (Some(year.parse().ok()?), rest),
+            words => (None, words),
+        };
+        let [day_word, day, part_word, part] = words else { return None };
+        if !day_word.eq_ignore_ascii_case("day") || !part_word.eq_ignore_ascii_case("part") {
+            return None;
+        }
+        Some(Self { year, day: day.parse().ok()?, part: part.parse().ok()? })
+    }
+}
+impl Display for BenchId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "{year} d{:02}p{}", self.day, self.part),
+            None => write!(f, "     d{:02}p{}", self.day, self.part),
+        }
+    }
+}
+
+/// A typical time along with the range it's likely to fall in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Estimate {
+    pub point: Duration,
+    pub lower: Duration,
+    pub upper: Duration,
+}
+
+pub type ResultSet = BTreeMap<BenchId, Estimate>;
+
+/// Loads a set of results, either a timing records file written by the `aoc`
+/// runner or `aoc_sol!` (`.jsonl`/`.csv`), or the name of a criterion
+/// baseline saved with `--save-baseline` under `criterion_dir`
+pub fn load(source: &str, criterion_dir: &Path) -> Result<ResultSet, String> {
+    if Path::new(source).is_file() {
+        load_records(Path::new(source))
+    } else {
+        load_criterion_baseline(criterion_dir, source)
+    }
+}
+
+/// Uses the fastest run as the lower bound, and mirrors it for the upper one
+fn load_records(path: &Path) -> Result<ResultSet, String> {
+    let mut results = ResultSet::new();
+    for record in aoc_tools::timing::load_records(path)? {
+        let id = BenchId { year: Some(record.year), day: record.day, part: record.part };
+        let point = Duration::from_nanos(record.median_ns);
+        let lower = Duration::from_nanos(record.min_ns);
+        results.insert(id, Estimate { point, lower, upper: point + (point - lower) });
+    }
+    Ok(results)
+}
+
+#[derive(Deserialize)]
+struct CriterionBenchmark {
+    full_id: String,
+}
+#[derive(Deserialize)]
+struct CriterionEstimates {
+    median: CriterionEstimate,
+}
+#[derive(Deserialize)]
+struct CriterionEstimate {
+    point_estimate: f64,
+    confidence_interval: CriterionInterval,
+}
+#[derive(Deserialize)]
+struct CriterionInterval {
+    lower_bound: f64,
+    upper_bound: f64,
+}
+
+fn load_criterion_baseline(criterion_dir: &Path, baseline: &str) -> Result<ResultSet, String> {
+    let read_json = |path: PathBuf| -> Result<String, String> {
+        std::fs::read_to_string(&path).map_err(|e| format!("Could not read {}: {e}", path.display()))
+    };
+    let entries = std::fs::read_dir(criterion_dir)
+        .map_err(|e| format!("Could not read {}: {e}", criterion_dir.display()))?;
+
+    let mut results = ResultSet::new();
+    for entry in entries {
+        let dir = entry.map_err(|e| e.to_string())?.path().join(baseline);
+        if !dir.join("estimates.json").is_file() { continue }
+
+        let benchmark: CriterionBenchmark = serde_json::from_str(&read_json(dir.join("benchmark.json"))?)
+            .map_err(|e| format!("Invalid benchmark.json in {}: {e}", dir.display()))?;
+        let Some(id) = BenchId::from_bench_name(&benchmark.full_id) else { continue };
+
+        let estimates: CriterionEstimates = serde_json::from_str(&read_json(dir.join("estimates.json"))?)
+            .map_err(|e| format!("Invalid estimates.json in {}: {e}", dir.display()))?;
+        let median = estimates.median;
+        results.insert(id, Estimate {
+            point: Duration::from_secs_f64(median.point_estimate / 1e9),
+            lower: Duration::from_secs_f64(median.confidence_interval.lower_bound / 1e9),
+            upper: Duration::from_secs_f64(median.confidence_interval.upper_bound / 1e9),
+        });
+    }
+    if results.is_empty() {
+        return Err(format!("No results for baseline `{baseline}` in {}", criterion_dir.display()));
+    }
+    Ok(results)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Significance {
+    Faster,
+    Slower,
+    Noise,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Delta {
+    pub id: BenchId,
+    pub baseline: Estimate,
+    pub candidate: Estimate,
+    /// Relative change in percent, positive when the candidate is slower, or
+    /// `None` if the baseline took no time so there's nothing to be relative to
+    pub change: Option<f64>,
+    pub significance: Significance,
+}
+impl Delta {
+    pub fn new(id: BenchId, baseline: Estimate, candidate: Estimate) -> Self {
+        let change = (!baseline.point.is_zero())
+            .then(|| (candidate.point.as_secs_f64() / baseline.point.as_secs_f64() - 1.0) * 100.0);
+        let significance = if candidate.lower > baseline.upper {
+            Significance::Slower
+        } else if candidate.upper < baseline.lower {
+            Significance::Faster
+        } else {
+            Significance::Noise
+        };
+        Self { id, baseline, candidate, change, significance }
+    }
+
+    /// Significantly slower by more than `threshold` percent, which any
+    /// significant slowdown from a zero baseline is
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.significance == Significance::Slower && self.change.is_none_or(|change| change > threshold)
+    }
+}
+
+pub struct Comparison {
+    pub deltas: Vec<Delta>,
+    pub only_baseline: Vec<BenchId>,
+    pub only_candidate: Vec<BenchId>,
+}
+
+pub fn compare(baseline: &ResultSet, candidate: &ResultSet) -> Comparison {
+    let deltas = baseline.iter()
+        .filter_map(|(id, base)| Some(Delta::new(*id, *base, *candidate.get(id)?)))
+        .collect();
+    let only_baseline = baseline.keys().filter(|id| !candidate.contains_key(id)).copied().collect();
+    let only_candidate = candidate.keys().filter(|id| !baseline.contains_key(id)).copied().collect();
+    Comparison { deltas, only_baseline, only_candidate }
+}
+
+impl Comparison {
+    /// Prints the delta table, returning how many regressions there were
+    pub fn print(&self, threshold: f64) -> usize {
+        println!("{:11}  {:>12}  {:>12}  {:>9}", "Day", "Baseline", "Candidate", "Change");
+        let mut regressions = 0;
+        for delta in &self.deltas {
+            let flag = match delta.significance {
+                _ if delta.is_regression(threshold) => {
+                    regressions += 1;
+                    "REGRESSED !!!!!"
+                },
+                Significance::Slower => "slower",
+                Significance::Faster => "faster",
+                Significance::Noise => "",
+            };
+            let change = match delta.change {
+                Some(change) => format!("{change:>+8.2}%"),
+                None => "n/a".to_string(),
+            };
+            let line = format!(
+                "{}  {:>12}  {:>12}  {change:>9}  {flag}",
+                delta.id,
+                format!("{:?}", delta.baseline.point),
+                format!("{:?}", delta.candidate.point),
+            );
+            println!("{}", line.trim_end());
+        }
+        for id in &self.only_baseline {
+            println!("{id}  only in baseline");
+        }
+        for id in &self.only_candidate {
+            println!("{id}  only in candidate");
+        }
+
+        let total = |estimate: fn(&Delta) -> Duration| self.deltas.iter().map(estimate).sum::<Duration>();
+        println!();
+        println!("Total: {:?} -> {:?}", total(|d| d.baseline.point), total(|d| d.candidate.point));
+        println!("{regressions} regression(s) past {threshold}%");
+        regressions
+    }
+}
+
+#[test]
+fn test_compare() {
+    assert_eq!(
+        BenchId::from_bench_name("2024 day 06 Part 2"),
+        Some(BenchId { year: Some(2024), day: 6, part: 2 }),
+    );
+    assert_eq!(BenchId::from_bench_name("Day 13 Part 1"), Some(BenchId { year: None, day: 13, part: 1 }));
+    assert_eq!(BenchId::from_bench_name("Multiday bench_2024"), None);
+
+    let estimate = |lower, point, upper| Estimate {
+        point: Duration::from_micros(point),
+        lower: Duration::from_micros(lower),
+        upper: Duration::from_micros(upper),
+    };
+    let id = BenchId { year: Some(2024), day: 6, part: 2 };
+
+    let slower = Delta::new(id, estimate(90, 100, 110), estimate(115, 120, 125));
+    assert_eq!(slower.significance, Significance::Slower);
+    assert!(slower.is_regression(5.0));
+    assert!(!slower.is_regression(25.0));
+
+    let noisy = Delta::new(id, estimate(90, 100, 110), estimate(100, 108, 130));
+    assert_eq!(noisy.significance, Significance::Noise);
+    assert!(!noisy.is_regression(5.0));
+
+    let faster = Delta::new(id, estimate(90, 100, 110), estimate(40, 50, 60));
+    assert_eq!(faster.significance, Significance::Faster);
+    assert_eq!(faster.change, Some(-50.0));
+
+    let from_zero = Delta::new(id, estimate(0, 0, 0), estimate(1, 2, 3));
+    assert_eq!(from_zero.change, None);
+    assert!(from_zero.is_regression(25.0));
+    assert_eq!(Delta::new(id, estimate(0, 0, 0), estimate(0, 0, 0)).significance, Significance::Noise);
+}
@@ -0,0 +1,6 @@
+pub mod compare;
+
+/// Which of the workspace's days are benchmarked, generated by build.rs
+pub mod registry {
+    include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+}
@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+
+use bench::{compare, registry};
+
+const USAGE: &str = "\
+Usage:
+    bench compare <baseline> <candidate> [--threshold <percent>] [--criterion-dir <dir>]
+    bench list
+
+<baseline> and <candidate> are timing record files (.jsonl/.csv) or the names
+of criterion baselines saved with `cargo bench -- --save-baseline <name>`";
+
+/// Regressions smaller than this (in percent) don't fail the comparison
+const DEFAULT_THRESHOLD: f64 = 5.0;
+
+fn default_criterion_dir() -> PathBuf {
+    let target_dir = std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../target")));
+    target_dir.join("criterion")
+}
+
+struct CompareOptions {
+    baseline: String,
+    candidate: String,
+    threshold: f64,
+    criterion_dir: PathBuf,
+}
+
+enum Command {
+    Compare(CompareOptions),
+    List,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    match args.next().as_deref() {
+        Some("compare") => (),
+        Some("list") => return Ok(Command::List),
+        Some(command) => return Err(format!("Unknown command `{command}`")),
+        None => return Err("Missing command".to_string()),
+    }
+
+    let mut positional = vec![];
+    let mut threshold = DEFAULT_THRESHOLD;
+    let mut criterion_dir = default_criterion_dir();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--threshold" => {
+                let value = args.next().ok_or("Missing percentage after --threshold")?;
+                threshold = value.trim_end_matches('%')
+                    .parse()
+                    .map_err(|_| format!("Invalid threshold `{value}`"))?;
+            },
+            "--criterion-dir" => {
+                criterion_dir = PathBuf::from(args.next().ok_or("Missing path after --criterion-dir")?);
+            },
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag `{flag}`")),
+            _ => positional.push(arg),
+        }
+    }
+    let [baseline, candidate] = <[String; 2]>::try_from(positional)
+        .map_err(|_| "Expected a baseline and a candidate".to_string())?;
+
+    Ok(Command::Compare(CompareOptions { baseline, candidate, threshold, criterion_dir }))
+}
+
+fn list() {
+    println!("Benchmarked:");
+    for (year, day) in registry::BENCHABLE {
+        println!("    {year} day {day:02}");
+    }
+    println!("Not benchmarked:");
+    for (year, day, reason) in registry::UNBENCHABLE {
+        println!("    {year} day {day:02}: {reason}");
+    }
+}
+
+fn compare(options: CompareOptions) {
+    let load = |source: &str| match compare::load(source, &options.criterion_dir) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        },
+    };
+    let baseline = load(&options.baseline);
+    let candidate = load(&options.candidate);
+
+    let regressions = compare::compare(&baseline, &candidate).print(options.threshold);
+    if regressions > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn main() {
+    match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Compare(options)) => compare(options),
+        Ok(Command::List) => list(),
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        },
+    }
+}
@@ -1,283 +1,4 @@
-use criterion::criterion_main;
-
 aoc_tools::arena!(arena);
 
-aoc_tools::day_bench!(day01 2024: day01_2024);
-aoc_tools::day_bench!(day02 2024: day02_2024);
-aoc_tools::day_bench!(day03 2024: day03_2024);
-aoc_tools::day_bench!(day04 2024: day04_2024);
-aoc_tools::day_bench!(day05 2024: day05_2024);
-aoc_tools::day_bench!(day06 2024: day06_2024);
-aoc_tools::day_bench!(day07 2024: day07_2024);
-aoc_tools::day_bench!(day08 2024: day08_2024);
-aoc_tools::day_bench!(day09 2024: day09_2024);
-aoc_tools::day_bench!(day10 2024: day10_2024);
-aoc_tools::day_bench!(day11 2024: day11_2024);
-aoc_tools::day_bench!(day12 2024: day12_2024);
-aoc_tools::day_bench!(day13 2024: day13_2024);
-aoc_tools::day_bench!(day14 2024: day14_2024);
-aoc_tools::day_bench!(day15 2024: day15_2024);
-aoc_tools::day_bench!(day16 2024: day16_2024);
-aoc_tools::day_bench!(day17 2024: day17_2024);
-aoc_tools::day_bench!(day18 2024: day18_2024);
-aoc_tools::day_bench!(day19 2024: day19_2024);
-aoc_tools::day_bench!(day20 2024: day20_2024);
-aoc_tools::day_bench!(day21 2024: day21_2024);
-aoc_tools::day_bench!(day22 2024: day22_2024);
-aoc_tools::day_bench!(day23 2024: day23_2024);
-aoc_tools::day_bench!(day24 2024: day24_2024);
-aoc_tools::day_bench!(day25 2024: day25_2024);
-
-aoc_tools::day_bench!(day01 2022: day01_2022);
-aoc_tools::day_bench!(day02 2022: day02_2022);
-aoc_tools::day_bench!(day16 2022: day16_2022);
-aoc_tools::day_bench!(day17 2022: day17_2022);
-aoc_tools::day_bench!(day18 2022: day18_2022);
-aoc_tools::day_bench!(day19 2022: day19_2022);
-aoc_tools::day_bench!(day20 2022: day20_2022);
-aoc_tools::day_bench!(day21 2022: day21_2022);
-aoc_tools::day_bench!(day22 2022: day22_2022);
-aoc_tools::day_bench!(day23 2022: day23_2022);
-aoc_tools::day_bench!(day24 2022: day24_2022);
-aoc_tools::day_bench!(day25 2022: day25_2022);
-
-aoc_tools::day_bench!(day08 2021: day08_2021);
-aoc_tools::day_bench!(day09 2021: day09_2021);
-aoc_tools::day_bench!(day10 2021: day10_2021);
-aoc_tools::day_bench!(day11 2021: day11_2021);
-aoc_tools::day_bench!(day12 2021: day12_2021);
-aoc_tools::day_bench!(day13 2021: day13_2021);
-aoc_tools::day_bench!(day14 2021: day14_2021);
-aoc_tools::day_bench!(day15 2021: day15_2021);
-aoc_tools::day_bench!(day16 2021: day16_2021);
-aoc_tools::day_bench!(day17 2021: day17_2021);
-aoc_tools::day_bench!(day18 2021: day18_2021);
-aoc_tools::day_bench!(day19 2021: day19_2021);
-aoc_tools::day_bench!(day20 2021: day20_2021);
-aoc_tools::day_bench!(day21 2021: day21_2021);
-aoc_tools::day_bench!(day22 2021: day22_2021);
-aoc_tools::day_bench!(day23 2021: day23_2021);
-aoc_tools::day_bench!(day24 2021: day24_2021);
-aoc_tools::day_bench!(day25 2021: day25_2021);
-
-aoc_tools::day_bench!(day01 2020: day01_2020);
-aoc_tools::day_bench!(day02 2020: day02_2020);
-aoc_tools::day_bench!(day03 2020: day03_2020);
-aoc_tools::day_bench!(day04 2020: day04_2020);
-aoc_tools::day_bench!(day05 2020: day05_2020);
-aoc_tools::day_bench!(day06 2020: day06_2020);
-aoc_tools::day_bench!(day07 2020: day07_2020);
-aoc_tools::day_bench!(day08 2020: day08_2020);
-aoc_tools::day_bench!(day09 2020: day09_2020);
-aoc_tools::day_bench!(day10 2020: day10_2020);
-aoc_tools::day_bench!(day11 2020: day11_2020);
-aoc_tools::day_bench!(day12 2020: day12_2020);
-aoc_tools::day_bench!(day13 2020: day13_2020);
-aoc_tools::day_bench!(day14 2020: day14_2020);
-aoc_tools::day_bench!(day15 2020: day15_2020);
-aoc_tools::day_bench!(day16 2020: day16_2020);
-aoc_tools::day_bench!(day17 2020: day17_2020);
-aoc_tools::day_bench!(day18 2020: day18_2020);
-aoc_tools::day_bench!(day19 2020: day19_2020);
-aoc_tools::day_bench!(day20 2020: day20_2020);
-aoc_tools::day_bench!(day21 2020: day21_2020);
-aoc_tools::day_bench!(day22 2020: day22_2020);
-aoc_tools::day_bench!(day23 2020: day23_2020);
-aoc_tools::day_bench!(day24 2020: day24_2020);
-aoc_tools::day_bench!(day25 2020: day25_2020);
-
-// aoc_tools::day_bench!(day01 2019: day01_2019);
-// aoc_tools::day_bench!(day02 2019: day02_2019);
-// aoc_tools::day_bench!(day03 2019: day03_2019);
-// aoc_tools::day_bench!(day04 2019: day04_2019);
-// aoc_tools::day_bench!(day05 2019: day05_2019);
-// aoc_tools::day_bench!(day06 2019: day06_2019);
-// aoc_tools::day_bench!(day07 2019: day07_2019);
-// aoc_tools::day_bench!(day08 2019: day08_2019);
-// aoc_tools::day_bench!(day09 2019: day09_2019);
-// aoc_tools::day_bench!(day10 2019: day10_2019);
-// aoc_tools::day_bench!(day11 2019: day11_2019);
-// aoc_tools::day_bench!(day12 2019: day12_2019);
-// aoc_tools::day_bench!(day13 2019: day13_2019);
-// aoc_tools::day_bench!(day14 2019: day14_2019);
-// aoc_tools::day_bench!(day15 2019: day15_2019);
-// aoc_tools::day_bench!(day16 2019: day16_2019);
-// aoc_tools::day_bench!(day17 2019: day17_2019);
-// aoc_tools::day_bench!(day18 2019: day18_2019);
-// aoc_tools::day_bench!(day19 2019: day19_2019);
-// aoc_tools::day_bench!(day20 2019: day20_2019);
-// aoc_tools::day_bench!(day21 2019: day21_2019);
-// aoc_tools::day_bench!(day22 2019: day22_2019);
-// aoc_tools::day_bench!(day23 2019: day23_2019);
-// aoc_tools::day_bench!(day24 2019: day24_2019);
-// aoc_tools::day_bench!(day25 2019: day25_2019);
-
-aoc_tools::day_bench!(day01 2018: day01_2018);
-aoc_tools::day_bench!(day02 2018: day02_2018);
-aoc_tools::day_bench!(day03 2018: day03_2018);
-aoc_tools::day_bench!(day04 2018: day04_2018);
-aoc_tools::day_bench!(day05 2018: day05_2018);
-aoc_tools::day_bench!(day06 2018: day06_2018);
-aoc_tools::day_bench!(day07 2018: day07_2018);
-aoc_tools::day_bench!(day08 2018: day08_2018);
-aoc_tools::day_bench!(day09 2018: day09_2018);
-aoc_tools::day_bench!(day10 2018: day10_2018);
-aoc_tools::day_bench!(day11 2018: day11_2018);
-aoc_tools::day_bench!(day12 2018: day12_2018);
-aoc_tools::day_bench!(day13 2018: day13_2018);
-aoc_tools::day_bench!(day14 2018: day14_2018);
-aoc_tools::day_bench!(day15 2018: day15_2018);
-aoc_tools::day_bench!(day16 2018: day16_2018);
-aoc_tools::day_bench!(day17 2018: day17_2018);
-aoc_tools::day_bench!(day18 2018: day18_2018);
-aoc_tools::day_bench!(day19 2018: day19_2018);
-aoc_tools::day_bench!(day20 2018: day20_2018);
-aoc_tools::day_bench!(day21 2018: day21_2018);
-aoc_tools::day_bench!(day22 2018: day22_2018);
-aoc_tools::day_bench!(day23 2018: day23_2018);
-aoc_tools::day_bench!(day24 2018: day24_2018);
-aoc_tools::day_bench!(day25 2018: day25_2018);
-
-criterion_main!(
-    // 2024
-    day01_2024::day01_2024,
-    day02_2024::day02_2024,
-    day03_2024::day03_2024,
-    day04_2024::day04_2024,
-    day05_2024::day05_2024,
-    day06_2024::day06_2024,
-    day07_2024::day07_2024,
-    day08_2024::day08_2024,
-    day09_2024::day09_2024,
-    day10_2024::day10_2024,
-    day11_2024::day11_2024,
-    day12_2024::day12_2024,
-    day13_2024::day13_2024,
-    day14_2024::day14_2024,
-    day15_2024::day15_2024,
-    day16_2024::day16_2024,
-    day17_2024::day17_2024,
-    day18_2024::day18_2024,
-    day19_2024::day19_2024,
-    day20_2024::day20_2024,
-    day21_2024::day21_2024,
-    day22_2024::day22_2024,
-    day23_2024::day23_2024,
-    day24_2024::day24_2024,
-    day25_2024::day25_2024,
-
-    // 2022
-    day01_2022::day01_2022,
-    day02_2022::day02_2022,
-    day16_2022::day16_2022,
-    day17_2022::day17_2022,
-    day18_2022::day18_2022,
-    day19_2022::day19_2022,
-    day20_2022::day20_2022,
-    day21_2022::day21_2022,
-    day22_2022::day22_2022,
-    day23_2022::day23_2022,
-    day24_2022::day24_2022,
-    day25_2022::day25_2022,
-
-    // 2021
-    day08_2021::day08_2021,
-    day09_2021::day09_2021,
-    day10_2021::day10_2021,
-    day11_2021::day11_2021,
-    day12_2021::day12_2021,
-    day13_2021::day13_2021,
-    day14_2021::day14_2021,
-    day15_2021::day15_2021,
-    day16_2021::day16_2021,
-    day17_2021::day17_2021,
-    day18_2021::day18_2021,
-    day19_2021::day19_2021,
-    day20_2021::day20_2021,
-    day21_2021::day21_2021,
-    day22_2021::day22_2021,
-    day23_2021::day23_2021,
-    day24_2021::day24_2021,
-    day25_2021::day25_2021,
-
-    // 2020
-    day01_2020::day01_2020,
-    day02_2020::day02_2020,
-    day03_2020::day03_2020,
-    day04_2020::day04_2020,
-    day05_2020::day05_2020,
-    day06_2020::day06_2020,
-    day07_2020::day07_2020,
-    day08_2020::day08_2020,
-    day09_2020::day09_2020,
-    day10_2020::day10_2020,
-    day11_2020::day11_2020,
-    day12_2020::day12_2020,
-    day13_2020::day13_2020,
-    day14_2020::day14_2020,
-    day15_2020::day15_2020,
-    day16_2020::day16_2020,
-    day17_2020::day17_2020,
-    day18_2020::day18_2020,
-    day19_2020::day19_2020,
-    day20_2020::day20_2020,
-    day21_2020::day21_2020,
-    day22_2020::day22_2020,
-    day23_2020::day23_2020,
-    day24_2020::day24_2020,
-    day25_2020::day25_2020,
-
-    // 2019
-    // day01_2019::day01_2019,
-    // day02_2019::day02_2019,
-    // day03_2019::day03_2019,
-    // day04_2019::day04_2019,
-    // day05_2019::day05_2019,
-    // day06_2019::day06_2019,
-    // day07_2019::day07_2019,
-    // day08_2019::day08_2019,
-    // day09_2019::day09_2019,
-    // day10_2019::day10_2019,
-    // day11_2019::day11_2019,
-    // day12_2019::day12_2019,
-    // day13_2019::day13_2019,
-    // day14_2019::day14_2019,
-    // day15_2019::day15_2019,
-    // day16_2019::day16_2019,
-    // day17_2019::day17_2019,
-    // day18_2019::day18_2019,
-    // day19_2019::day19_2019,
-    // day20_2019::day20_2019,
-    // day21_2019::day21_2019,
-    // day22_2019::day22_2019,
-    // day23_2019::day23_2019,
-    // day24_2019::day24_2019,
-    // day25_2019::day25_2019,
-
-    // 2018
-    day01_2018::day01_2018,
-    day02_2018::day02_2018,
-    day03_2018::day03_2018,
-    day04_2018::day04_2018,
-    day05_2018::day05_2018,
-    day06_2018::day06_2018,
-    day07_2018::day07_2018,
-    day08_2018::day08_2018,
-    day09_2018::day09_2018,
-    day10_2018::day10_2018,
-    day11_2018::day11_2018,
-    day12_2018::day12_2018,
-    day13_2018::day13_2018,
-    day14_2018::day14_2018,
-    day15_2018::day15_2018,
-    day16_2018::day16_2018,
-    day17_2018::day17_2018,
-    day18_2018::day18_2018,
-    day19_2018::day19_2018,
-    day20_2018::day20_2018,
-    day21_2018::day21_2018,
-    day22_2018::day22_2018,
-    day23_2018::day23_2018,
-    day24_2018::day24_2018,
-    day25_2018::day25_2018,
-);
+// A `day_bench!` for every benchable day in the workspace, generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/separate_bench.rs"));
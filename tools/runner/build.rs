@@ -0,0 +1,37 @@
+use std::fmt::Write;
+use std::path::Path;
+
+/// Generates `registry.rs`, listing every day crate in the workspace whose
+/// `aoc_sol!` gives it `AOC_PARTS`
+fn main() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let manifest_dir = Path::new(&manifest_dir);
+    let workspace_dir = manifest_dir.join("../..");
+    let manifest_path = manifest_dir.join("Cargo.toml");
+    println!("cargo:rerun-if-changed={}", manifest_path.display());
+    println!("cargo:rerun-if-changed={}", workspace_dir.join("Cargo.toml").display());
+
+    let manifest = aoc_workspace::read_manifest(&manifest_path).unwrap_or_else(|e| panic!("{e}"));
+    let days = aoc_workspace::day_crates(&workspace_dir).unwrap_or_else(|e| panic!("{e}"));
+    let missing = aoc_workspace::missing_dependencies(&manifest, &days);
+    for day in &missing {
+        println!("cargo:warning={} isn't a dependency yet, so it can't be run. Run `cargo aoc-workspace sync`.", day.name());
+    }
+
+    let mut registry = String::from("pub const DAYS: &[Day] = &[\n");
+    for day in &days {
+        println!("cargo:rerun-if-changed={}", workspace_dir.join(&day.path).join("src/lib.rs").display());
+        if day.parts.is_err() || missing.contains(&day) { continue }
+        writeln!(
+            registry,
+            "    Day {{ year: {}, day: {}, params: ::{module}::AOC_PARAMS, parts: ::{module}::AOC_PARTS }},",
+            day.year,
+            day.day,
+            module = day.module(),
+        ).unwrap();
+    }
+    registry.push_str("];\n");
+
+    let out_path = Path::new(&std::env::var("OUT_DIR").unwrap()).join("registry.rs");
+    std::fs::write(out_path, registry).unwrap();
+}
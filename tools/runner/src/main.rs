@@ -0,0 +1,408 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aoc_tools::answers::{Answers, Verification};
+use aoc_tools::input::InputSource;
+use aoc_tools::params::Preset;
+use aoc_tools::timing::{self, Timing, TimingRecord};
+use registry::{Day, DAYS};
+
+mod registry;
+
+const DATA_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../data");
+const WORKSPACE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../..");
+
+const USAGE: &str = "\
+Usage:
+    aoc run (--all | <year> [<day>]) [--part <n>] [--test | --input <path> | --stdin] [--verify | --record]
+            [--iterations <n>] [--timings <path.jsonl | path.csv>]
+    aoc times <path.jsonl | path.csv> [<year>] [--write]
+    aoc list";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Selection {
+    All,
+    Year(u16),
+    Day(u16, u8),
+}
+impl Selection {
+    fn matches(&self, day: &Day) -> bool {
+        match *self {
+            Self::All => true,
+            Self::Year(year) => day.year == year,
+            Self::Day(year, d) => day.year == year && day.day == d,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct RunOptions {
+    selection: Selection,
+    part: Option<usize>,
+    source: InputSource,
+    verify: bool,
+    /// Store the answers as the known ones, e.g. after they were accepted on the site
+    record: bool,
+    iterations: u32,
+    timings: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run(RunOptions),
+    Times { records: PathBuf, year: Option<u16>, write: bool },
+    List,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    match args.next().as_deref() {
+        Some("run") => (),
+        Some("list") => return Ok(Command::List),
+        Some("times") => return parse_times_args(args),
+        Some(command) => return Err(format!("Unknown command `{command}`")),
+        None => return Err("Missing command".to_string()),
+    }
+
+    let mut all = false;
+    let mut positional = vec![];
+    let mut part = None;
+    let mut source = None;
+    let mut verify = false;
+    let mut record = false;
+    let mut iterations = 1;
+    let mut timings = None;
+    while let Some(arg) = args.next() {
+        let new_source = match arg.as_str() {
+            "--all" => { all = true; continue },
+            "--verify" => { verify = true; continue },
+            "--record" => { record = true; continue },
+            "--part" => {
+                let value = args.next().ok_or("Missing part number after --part")?;
+                part = Some(value.parse().map_err(|_| format!("Invalid part number `{value}`"))?);
+                continue;
+            },
+            "--iterations" => {
+                let value = args.next().ok_or("Missing count after --iterations")?;
+                iterations = value.parse().map_err(|_| format!("Invalid iteration count `{value}`"))?;
+                continue;
+            },
+            "--timings" => {
+                timings = Some(PathBuf::from(args.next().ok_or("Missing path after --timings")?));
+                continue;
+            },
+            "--test" => InputSource::Test,
+            "--stdin" => InputSource::Stdin,
+            "--input" => match args.next().ok_or("Missing path after --input")?.as_str() {
+                "-" => InputSource::Stdin,
+                path => InputSource::Path(PathBuf::from(path)),
+            },
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag `{flag}`")),
+            _ => { positional.push(arg); continue },
+        };
+        if source.replace(new_source).is_some() {
+            return Err("Only one of --input, --stdin or --test may be given".to_string());
+        }
+    }
+
+    let selection = match (all, positional.as_slice()) {
+        (true, []) => Selection::All,
+        (true, _) => return Err("--all can't be combined with a year or day".to_string()),
+        (false, [year]) => Selection::Year(parse_year(year)?),
+        (false, [year, day]) => Selection::Day(parse_year(year)?, parse_day(day)?),
+        (false, []) => return Err("Missing year (or --all)".to_string()),
+        (false, _) => return Err("Too many arguments".to_string()),
+    };
+    let source = source.unwrap_or(InputSource::Data);
+    if !matches!(selection, Selection::Day(..)) && !matches!(source, InputSource::Data | InputSource::Test) {
+        return Err("--input and --stdin can only be used when running a single day".to_string());
+    }
+
+    if record && source != InputSource::Data {
+        return Err("--record only records answers for the real input".to_string());
+    }
+    if verify && source != InputSource::Data {
+        return Err("--verify only checks answers for the real input".to_string());
+    }
+    if record && verify {
+        return Err("--record and --verify can't be combined".to_string());
+    }
+
+    Ok(Command::Run(RunOptions { selection, part, source, verify, record, iterations, timings }))
+}
+
+fn parse_times_args(args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut records = None;
+    let mut year = None;
+    let mut write = false;
+    for arg in args {
+        match arg.as_str() {
+            "--write" => write = true,
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag `{flag}`")),
+            _ if records.is_none() => records = Some(PathBuf::from(arg)),
+            _ if year.is_none() => year = Some(parse_year(&arg)?),
+            _ => return Err("Too many arguments".to_string()),
+        }
+    }
+    let records = records.ok_or("Missing timing records path")?;
+    Ok(Command::Times { records, year, write })
+}
+
+fn parse_year(year: &str) -> Result<u16, String> {
+    match year.parse() {
+        Ok(year) if year >= 2015 => Ok(year),
+        _ => Err(format!("Invalid year `{year}`. Make sure you use all 4 digits of the year")),
+    }
+}
+fn parse_day(day: &str) -> Result<u8, String> {
+    match day.trim_start_matches("day").parse() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!("Invalid day `{day}`")),
+    }
+}
+
+enum Outcome {
+    Answer { answer: String, timing: Timing, verification: Option<Verification> },
+    MissingInput(String),
+    Panicked,
+}
+
+struct Row {
+    day: Day,
+    part: usize,
+    outcome: Outcome,
+}
+
+fn run(options: &RunOptions) -> Vec<Row> {
+    let mut rows = vec![];
+    let preset = Preset::for_source(&options.source);
+    for day in DAYS.iter().filter(|day| options.selection.matches(day)) {
+        let parts = day.selected_parts(options.part);
+
+        let input = options.source.read(DATA_DIR, &day.day_path());
+        let answers = if options.verify {
+            Some(Answers::load(&Answers::path(DATA_DIR, &day.day_path())).unwrap_or_else(|e| panic!("{e}")))
+        } else {
+            None
+        };
+
+        // Built once, so the timings are just the parts. Parts only read them,
+        // so a panicking part can't leave them broken for the next.
+        let params = (day.params)(&preset).unwrap_or_else(|e| panic!("{day:?}: {e}"));
+        let params = std::panic::AssertUnwindSafe(&params);
+
+        for (part, part_fn) in parts {
+            let input = match &input {
+                Ok(input) => input.as_str(),
+                Err(e) => {
+                    rows.push(Row { day: *day, part, outcome: Outcome::MissingInput(e.to_string()) });
+                    continue;
+                },
+            };
+
+            let outcome = match std::panic::catch_unwind(|| {
+                timing::measure(options.iterations, || part_fn(input, *params).to_string())
+            }) {
+                Ok((answer, timing)) => {
+                    let verification = answers.as_ref().map(|answers| answers.verify(part, &answer));
+                    Outcome::Answer { answer, timing, verification }
+                },
+                Err(_) => Outcome::Panicked,
+            };
+            rows.push(Row { day: *day, part, outcome });
+        }
+    }
+    rows
+}
+
+/// Prints the results, returning whether every part produced a (correct) answer
+fn print_table(rows: &[Row]) -> bool {
+    let answer_text = |answer: &str| match answer.trim().split_once('\n') {
+        Some((first_line, _)) => format!("{first_line}..."),
+        None => answer.trim().to_string(),
+    };
+    let answer_width = rows.iter()
+        .map(|row| match &row.outcome {
+            Outcome::Answer { answer, .. } => answer_text(answer).chars().count(),
+            _ => 0,
+        })
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+
+    println!("Year  Day  Part  {:answer_width$}  {:>12}", "Answer", "Time");
+    let mut all_ok = true;
+    let mut total = Duration::ZERO;
+    for Row { day, part, outcome } in rows {
+        print!("{}   {:02}  {part:>4}  ", day.year, day.day);
+        match outcome {
+            Outcome::Answer { answer, timing, verification } => {
+                total += timing.median;
+                print!("{:answer_width$}  {:>12}", answer_text(answer), format!("{:?}", timing.median));
+                match verification {
+                    Some(Verification::Correct) => println!("  ok"),
+                    Some(Verification::Incorrect { expected, .. }) => {
+                        all_ok = false;
+                        println!("  WRONG (expected {expected})");
+                    },
+                    Some(Verification::Unknown) => println!("  ?"),
+                    None => println!(),
+                }
+            },
+            Outcome::MissingInput(e) => {
+                all_ok = false;
+                println!("missing input ({e})");
+            },
+            Outcome::Panicked => {
+                all_ok = false;
+                println!("panicked");
+            },
+        }
+    }
+    println!("Total: {total:?}");
+    all_ok
+}
+
+fn record_answers(rows: &[Row]) {
+    for Row { day, part, outcome } in rows {
+        let Outcome::Answer { answer, .. } = outcome else { continue };
+        let path = Answers::path(DATA_DIR, &day.day_path());
+        let mut answers = Answers::load(&path).unwrap_or_else(|e| panic!("{e}"));
+        match answers.set(*part, answer.clone()) {
+            Some(prev) if prev != *answer => println!("{day:?} part {part} answer changed: {prev} -> {answer}"),
+            Some(_) => (),
+            None => println!("{day:?} part {part} answer recorded: {answer}"),
+        }
+        answers.save(&path).unwrap_or_else(|e| panic!("Could not write {}: {e}", path.display()));
+    }
+}
+
+fn write_timings(path: &std::path::Path, rows: &[Row]) {
+    let commit = timing::git_commit();
+    let records: Vec<_> = rows.iter()
+        .filter_map(|Row { day, part, outcome }| match outcome {
+            Outcome::Answer { answer, timing, .. } => Some(
+                TimingRecord::new(day.year, day.day, *part as u8, timing, answer, commit.clone())
+            ),
+            _ => None,
+        })
+        .collect();
+    if let Err(e) = timing::append_records(path, &records) {
+        eprintln!("Could not write timings to {}: {e}", path.display());
+        std::process::exit(1);
+    }
+}
+
+fn times(records_path: &std::path::Path, year: Option<u16>, write: bool) {
+    let records = match timing::load_records(records_path) {
+        Ok(records) => records,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        },
+    };
+    let mut years: Vec<_> = records.iter().map(|record| record.year).collect();
+    years.sort();
+    years.dedup();
+
+    for year in years.into_iter().filter(|y| year.is_none_or(|year| year == *y)) {
+        let summary = timing::summary(&records, year, timing::SLOW_THRESHOLD);
+        if write {
+            let path = format!("{WORKSPACE_DIR}/{year}-times.txt");
+            if let Err(e) = std::fs::write(&path, summary) {
+                eprintln!("Could not write {path}: {e}");
+                std::process::exit(1);
+            }
+            println!("Wrote {year}-times.txt");
+        } else {
+            println!("{summary}");
+        }
+    }
+}
+
+fn main() {
+    let command = match parse_args(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        },
+    };
+
+    match command {
+        Command::List => {
+            for day in DAYS {
+                println!("{day:?} ({} parts)", day.parts.len());
+            }
+        },
+        Command::Times { records, year, write } => times(&records, year, write),
+        Command::Run(options) => {
+            let rows = run(&options);
+            if rows.is_empty() {
+                eprintln!("No registered days match {:?}", options.selection);
+                std::process::exit(1);
+            }
+            if let Some(path) = &options.timings {
+                write_timings(path, &rows);
+            }
+            if options.record {
+                record_answers(&rows);
+            }
+            if !print_table(&rows) {
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+#[test]
+fn test_parse_args() {
+    let parse = |args: &str| parse_args(args.split_whitespace().map(|arg| arg.to_string()));
+    let run = |args: &str| match parse(args) {
+        Ok(Command::Run(options)) => options,
+        other => panic!("`{args}` parsed as {other:?}"),
+    };
+
+    let options = run("run 2024 day06 --part 2 --test --iterations 5 --timings t.jsonl");
+    assert_eq!(options.selection, Selection::Day(2024, 6));
+    assert_eq!((options.part, options.iterations), (Some(2), 5));
+    assert_eq!(options.source, InputSource::Test);
+    assert_eq!(options.timings, Some(PathBuf::from("t.jsonl")));
+    assert_eq!(run("run 2019").selection, Selection::Year(2019));
+    assert_eq!(run("run --all --verify").selection, Selection::All);
+    assert_eq!(run("run 2024 6 --input -").source, InputSource::Stdin);
+    assert!(run("run 2024 6 --record").record);
+    assert_eq!(parse("list"), Ok(Command::List));
+    assert_eq!(
+        parse("times t.csv 2024 --write"),
+        Ok(Command::Times { records: PathBuf::from("t.csv"), year: Some(2024), write: true }),
+    );
+
+    for bad in [
+        "", "walk", "run", "run 24", "run 2024 26", "run --all 2024", "run 2024 1 2",
+        "run 2024 --input x.txt", "run 2024 1 --test --stdin", "run 2024 1 --part",
+        "run 2024 1 --frobnicate", "run 2024 1 --record --test", "run 2024 1 --record --verify",
+        "run 2024 1 --verify --test", "run 2024 1 --verify --input x.txt",
+    ] {
+        assert!(parse(bad).is_err(), "`{bad}` should be rejected");
+    }
+}
+
+#[test]
+fn test_selection() {
+    use aoc_tools::{answers::Answer, params::{DayParams, PartFn}};
+    fn length(input: &str, _: &DayParams) -> Answer {
+        input.len().into()
+    }
+    static PARTS: [PartFn; 2] = [length, length];
+    let day = Day { year: 2024, day: 6, params: DayParams::from_preset::<()>, parts: &PARTS };
+
+    assert!(Selection::All.matches(&day));
+    assert!(Selection::Year(2024).matches(&day) && !Selection::Year(2019).matches(&day));
+    assert!(Selection::Day(2024, 6).matches(&day) && !Selection::Day(2024, 7).matches(&day));
+
+    let parts = |part| day.selected_parts(part).map(|(i, _)| i).collect::<Vec<_>>();
+    assert_eq!(parts(None), [1, 2]);
+    assert_eq!(parts(Some(2)), [2]);
+    assert!(parts(Some(3)).is_empty());
+}
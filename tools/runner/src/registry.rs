@@ -0,0 +1,32 @@
+use aoc_tools::params::{ParamsFn, PartFn};
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub params: ParamsFn,
+    pub parts: &'static [PartFn],
+}
+
+impl Day {
+    pub fn day_path(&self) -> String {
+        format!("{}/day{:02}", self.year, self.day)
+    }
+
+    /// Each part numbered from 1, or just `part` if there's a filter
+    pub fn selected_parts(&self, part: Option<usize>) -> impl Iterator<Item = (usize, &PartFn)> {
+        self.parts
+            .iter()
+            .enumerate()
+            .map(|(i, part_fn)| (i + 1, part_fn))
+            .filter(move |(i, _)| part.is_none_or(|part| part == *i))
+    }
+}
+
+impl std::fmt::Debug for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {:02}", self.year, self.day)
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
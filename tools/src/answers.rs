@@ -0,0 +1,177 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use crate::ocr::PixelGrid;
+
+/// The value a part produces. Grids of letters are read with OCR, so they can
+/// be compared with the recorded answers like any other value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    Str(String),
+    Grid(PixelGrid),
+}
+
+macro_rules! answer_from_int {
+    ($($int:ty),+) => {$(
+        impl From<$int> for Answer {
+            fn from(value: $int) -> Self {
+                Self::Int(value as i128)
+            }
+        }
+    )+};
+}
+answer_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Str(value)
+    }
+}
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+impl From<PixelGrid> for Answer {
+    fn from(value: PixelGrid) -> Self {
+        Self::Grid(value)
+    }
+}
+
+/// Grids that can't be read are shown on their own lines
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Str(value) => write!(f, "{value}"),
+            Self::Grid(grid) => match grid.ocr() {
+                Some(letters) => write!(f, "{letters}"),
+                None => write!(f, "\n{grid}"),
+            },
+        }
+    }
+}
+
+/// Known answers for a day, stored in `data/<year>/<day>/answers.toml`:
+///
+/// ```toml
+/// part1 = "1234"
+/// part2 = "ABCDEFGH"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    parts: Vec<Option<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    Correct,
+    Incorrect { expected: String, actual: String },
+    Unknown,
+}
+
+impl Answers {
+    pub fn path(default_dir: &str, day_path: &str) -> PathBuf {
+        crate::input::data_path(default_dir, day_path, "answers.toml")
+    }
+
+    /// A missing file is treated as having no known answers
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("Could not read {}: {e}", path.display())),
+        };
+        Self::parse(&contents).map_err(|e| format!("Could not parse {}: {e}", path.display()))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let table: toml::Table = contents.parse().map_err(|e: toml::de::Error| e.message().to_string())?;
+        Self::from_table(table)
+    }
+
+    /// Reads `partN` keys, rejecting anything else
+    pub fn from_table(table: toml::Table) -> Result<Self, String> {
+        let mut answers = Self::default();
+        for (key, value) in table {
+            let Some(part) = key.strip_prefix("part").and_then(|part| part.parse().ok()).filter(|&part: &usize| part > 0) else {
+                return Err(format!("Unexpected key `{key}`"));
+            };
+            let value = match value {
+                toml::Value::String(s) => s,
+                toml::Value::Integer(i) => i.to_string(),
+                v => return Err(format!("Answer for `{key}` must be a string or integer, not {}", v.type_str())),
+            };
+            answers.set(part, value);
+        }
+        Ok(answers)
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let mut table = toml::Table::new();
+        for (i, answer) in self.parts.iter().enumerate() {
+            let Some(answer) = answer else { continue };
+            table.insert(format!("part{}", i + 1), toml::Value::String(answer.clone()));
+        }
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, table.to_string())
+    }
+
+    pub fn get(&self, part: usize) -> Option<&str> {
+        self.parts.get(part.checked_sub(1)?)?.as_deref()
+    }
+
+    pub fn set(&mut self, part: usize, answer: String) -> Option<String> {
+        let idx = part.checked_sub(1).expect("Parts are numbered from 1");
+        if self.parts.len() <= idx {
+            self.parts.resize(idx + 1, None);
+        }
+        self.parts[idx].replace(answer)
+    }
+
+    pub fn verify(&self, part: usize, actual: &str) -> Verification {
+        match self.get(part) {
+            None => Verification::Unknown,
+            Some(expected) if expected.trim() == actual.trim() => Verification::Correct,
+            Some(expected) => Verification::Incorrect {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            },
+        }
+    }
+}
+
+/// Stores `values` as the known answers for the day, e.g. after they were
+/// accepted on the site
+pub fn record_answers(default_dir: &str, day_path: &str, values: impl Iterator<Item = String>) {
+    let path = Answers::path(default_dir, day_path);
+    let mut answers = Answers::load(&path).unwrap_or_else(|e| panic!("{e}"));
+    for (i, value) in values.enumerate() {
+        match answers.set(i + 1, value.clone()) {
+            Some(prev) if prev != value => println!("Part {} answer changed: {prev} -> {value}", i + 1),
+            Some(_) => (),
+            None => println!("Part {} answer recorded: {value}", i + 1),
+        }
+    }
+    answers.save(&path).unwrap_or_else(|e| panic!("Could not write {}: {e}", path.display()));
+}
+
+#[test]
+fn test_answers_parse() {
+    let answers = Answers::parse("part1 = 1234\npart2 = \"ABC\"\n").unwrap();
+    assert_eq!(answers.get(1), Some("1234"));
+    assert_eq!(answers.get(2), Some("ABC"));
+    assert_eq!(answers.get(3), None);
+
+    assert_eq!(answers.verify(1, "1234"), Verification::Correct);
+    assert_eq!(answers.verify(1, "123"), Verification::Incorrect { expected: "1234".into(), actual: "123".into() });
+    assert_eq!(answers.verify(3, "1"), Verification::Unknown);
+    assert!(Answers::parse("answer = 1").is_err());
+
+    let grid = PixelGrid::from_art("#..#\n#..#\n####\n#..#\n#..#\n#..#");
+    assert_eq!(Answer::from(grid).to_string(), "H");
+    assert_eq!(Answer::from(-12_i64).to_string(), "-12");
+}
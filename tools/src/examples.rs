@@ -0,0 +1,175 @@
+use std::path::{Path, PathBuf};
+
+use crate::answers::{Answers, Verification};
+use crate::params::{ParamsFn, PartFn, Preset};
+
+/// A puzzle example along with the answers it should give, stored in
+/// `data/<year>/<day>/examples.toml` as one table per example:
+///
+/// ```toml
+/// [small]
+/// file = "test.txt"
+/// part1 = 12
+///
+/// [wide]
+/// input = """
+/// p=0,4 v=3,-3
+/// """
+/// part2 = 7
+///
+/// [wide.params]
+/// width = 11
+/// height = 7
+/// ```
+///
+/// `file` is relative to the directory containing `examples.toml`, `input` is
+/// the example itself. Parts without an expected answer aren't run, e.g. for
+/// examples that only apply to part 2. Every example must be named in the
+/// day's `aoc_sol!(...; examples: ...)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub answers: Answers,
+    /// Puzzle-specific values (grid size, step count, ...) the example uses
+    /// instead of the real input's
+    pub params: toml::Table,
+}
+
+impl Example {
+    pub fn path(default_dir: &str, day_path: &str) -> PathBuf {
+        crate::input::data_path(default_dir, day_path, "examples.toml")
+    }
+
+    pub fn load_all(path: &Path) -> Result<Vec<Self>, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        Self::parse_all(&contents, dir).map_err(|e| format!("Could not parse {}: {e}", path.display()))
+    }
+
+    pub fn load(path: &Path, name: &str) -> Result<Self, String> {
+        Self::load_all(path)?
+            .into_iter()
+            .find(|example| example.name == name)
+            .ok_or_else(|| format!("No example `{name}` in {}", path.display()))
+    }
+
+    /// `dir` is where `file`s are looked up
+    pub fn parse_all(contents: &str, dir: &Path) -> Result<Vec<Self>, String> {
+        let table: toml::Table = contents.parse().map_err(|e: toml::de::Error| e.message().to_string())?;
+        table.into_iter()
+            .map(|(name, value)| {
+                let toml::Value::Table(mut table) = value else {
+                    return Err(format!("Example `{name}` must be a table"));
+                };
+                let input = match (table.remove("file"), table.remove("input")) {
+                    (Some(toml::Value::String(file)), None) => std::fs::read_to_string(dir.join(&file))
+                        .map_err(|e| format!("Could not read {file} for example `{name}`: {e}"))?,
+                    (None, Some(toml::Value::String(input))) => input,
+                    _ => return Err(format!("Example `{name}` needs either a `file` or an `input` string")),
+                };
+                let params = match table.remove("params") {
+                    Some(toml::Value::Table(params)) => params,
+                    Some(_) => return Err(format!("`params` of example `{name}` must be a table")),
+                    None => toml::Table::new(),
+                };
+                let answers = Answers::from_table(table).map_err(|e| format!("Example `{name}`: {e}"))?;
+                Ok(Self { name, input, answers, params })
+            })
+            .collect()
+    }
+
+    /// Runs every part with an expected answer, returning the mismatches
+    pub fn check(&self, params_fn: ParamsFn, parts: &[PartFn]) -> Vec<String> {
+        let params = match params_fn(&Preset::Example(&self.params)) {
+            Ok(params) => params,
+            Err(e) => return vec![format!("Invalid params: {e}")],
+        };
+        let mut errors = vec![];
+        for (i, part_fn) in parts.iter().enumerate() {
+            if self.answers.get(i + 1).is_none() { continue }
+            let actual = part_fn(&self.input, &params).to_string();
+            if let Verification::Incorrect { expected, actual } = self.answers.verify(i + 1, &actual) {
+                errors.push(format!("Part {}: expected {expected}, got {actual}", i + 1));
+            }
+        }
+        errors
+    }
+}
+
+/// The body of the `#[test]` generated for each example named in `aoc_sol!`
+pub fn test_example(default_dir: &str, day_path: &str, name: &str, params_fn: ParamsFn, parts: &[PartFn]) {
+    let example = Example::load(&Example::path(default_dir, day_path), name).unwrap_or_else(|e| panic!("{e}"));
+    assert!(
+        (1..=parts.len()).any(|part| example.answers.get(part).is_some()),
+        "Example `{name}` has no expected answers",
+    );
+    let errors = example.check(params_fn, parts);
+    assert!(errors.is_empty(), "Example `{name}` failed:\n{}", errors.join("\n"));
+}
+
+/// The body of the `#[test]` generated for every day, failing if the day's
+/// `examples.toml` has examples that aren't `listed` in its `aoc_sol!`
+pub fn test_examples_listed(default_dir: &str, day_path: &str, listed: &[&str]) {
+    let path = Example::path(default_dir, day_path);
+    if !path.exists() { return }
+    let unlisted = unlisted_examples(&path, listed).unwrap_or_else(|e| panic!("{e}"));
+    assert!(
+        unlisted.is_empty(),
+        "{} has examples missing from `aoc_sol!(...; examples: ...)`: {}",
+        path.display(),
+        unlisted.join(", "),
+    );
+}
+
+fn unlisted_examples(path: &Path, listed: &[&str]) -> Result<Vec<String>, String> {
+    Ok(Example::load_all(path)?
+        .into_iter()
+        .map(|example| example.name)
+        .filter(|name| !listed.contains(&name.as_str()))
+        .collect())
+}
+
+#[test]
+fn test_examples_parse() {
+    let examples = Example::parse_all(
+        "[small]\ninput = \"1 2 3\"\npart1 = 6\n\n[big]\ninput = \"4 5\"\npart2 = \"20\"\nparams = { limit = 3 }\n",
+        Path::new("."),
+    ).unwrap();
+    let real_params: ParamsFn = |_| crate::params::DayParams::from_preset::<()>(&Preset::Real);
+    let no_params: ParamsFn = crate::params::DayParams::from_preset::<()>;
+    let sum: PartFn = |input, _| {
+        input.split(' ').map(|n| n.parse::<u32>().unwrap()).sum::<u32>().into()
+    };
+    let product: PartFn = |input, _| {
+        input.split(' ').map(|n| n.parse::<u32>().unwrap()).product::<u32>().into()
+    };
+
+    let small = examples.iter().find(|example| example.name == "small").unwrap();
+    assert!(small.check(no_params, &[sum, |_, _| unreachable!("part 2 has no expected answer")]).is_empty());
+
+    let big = examples.iter().find(|example| example.name == "big").unwrap();
+    assert_eq!(big.params.get("limit").and_then(|limit| limit.as_integer()), Some(3));
+    assert_eq!(big.check(real_params, &[sum, sum]), vec!["Part 2: expected 20, got 9".to_string()]);
+    assert!(big.check(real_params, &[sum, product]).is_empty());
+    assert_eq!(
+        big.check(no_params, &[sum, product]),
+        vec!["Invalid params: Unknown parameter `limit` (the day has no parameters)".to_string()],
+    );
+
+    assert!(Example::parse_all("[broken]\npart1 = 1\n", Path::new(".")).is_err());
+}
+
+#[test]
+fn test_unlisted_examples() {
+    let dir = std::env::temp_dir().join(format!("aoc_examples_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("examples.toml");
+    std::fs::write(&path, "[small]\ninput = \"1\"\npart1 = 1\n\n[big]\ninput = \"2\"\npart1 = 2\n").unwrap();
+
+    assert!(unlisted_examples(&path, &["big", "small"]).unwrap().is_empty());
+    assert_eq!(unlisted_examples(&path, &["small"]).unwrap(), vec!["big".to_string()]);
+    assert_eq!(unlisted_examples(&path, &[]).unwrap().len(), 2);
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -1,45 +1,91 @@
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::hash::Hash;
+use std::marker::PhantomData;
 use std::ops::Add;
 
+use crate::search::{Queued, StateMap};
+
+mod algorithms;
+mod dense;
+mod routes;
+mod text;
+
+pub use algorithms::GraphAlgorithms;
+pub use dense::DenseGraph;
+pub use routes::{Distances, SubsetCosts};
+
+/// Whether a `Graph`'s edges go one way or both ways
+pub trait Direction {
+    const DIRECTED: bool;
+}
+#[derive(Debug, Clone, Copy)]
+pub struct Undirected;
+#[derive(Debug, Clone, Copy)]
+pub struct Directed;
+impl Direction for Undirected {
+    const DIRECTED: bool = false;
+}
+impl Direction for Directed {
+    const DIRECTED: bool = true;
+}
+
+pub type DiGraph<Id, N, E> = Graph<Id, N, E, Directed>;
+
+/// Node data, out-edges and in-edges. Undirected graphs only use the out-edges,
+/// as every edge is an out-edge of both of its nodes.
+type NodeEntry<Id, N> = (N, HashMap<Id, usize>, HashMap<Id, usize>);
+
 #[derive(Debug)]
-pub struct Graph<Id: Hash + Eq + Copy, N, E> {
-    nodes: HashMap<Id, (N, HashMap<Id, usize>)>,
+pub struct Graph<Id: Hash + Eq + Copy, N, E, D: Direction = Undirected> {
+    nodes: HashMap<Id, NodeEntry<Id, N>>,
     edge_data: HashMap<usize, E>,
     next_edge_data_id: usize,
+    direction: PhantomData<D>,
 }
 
-impl<Id: Hash + Eq + Copy, N, E> Graph<Id, N, E> {
+impl<Id: Hash + Eq + Copy, N, E, D: Direction> Graph<Id, N, E, D> {
     pub fn new() -> Self {
         Self {
             nodes: HashMap::new(),
             edge_data: HashMap::new(),
             next_edge_data_id: 0,
+            direction: PhantomData,
         }
     }
     pub fn insert_or_update_node(&mut self, id: Id, data: N) {
         match self.nodes.get_mut(&id) {
-            Some((data_ref, _)) => *data_ref = data,
-            None => { self.nodes.insert(id, (data, HashMap::new())); }
+            Some((data_ref, ..)) => *data_ref = data,
+            None => { self.nodes.insert(id, (data, HashMap::new(), HashMap::new())); }
         }
     }
     pub fn insert_node(&mut self, id: Id, data: N) -> Option<(N, Vec<((Id, Id), E)>)> {
-        let (old_data, old_edges) = self.nodes.insert(id, (data, HashMap::new()))?;
-        let removed_edges = self.remove_node_bits(id, old_edges);
+        let (old_data, old_edges, old_in_edges) = self.nodes.insert(id, (data, HashMap::new(), HashMap::new()))?;
+        let removed_edges = self.remove_node_bits(id, old_edges, old_in_edges);
         Some((old_data, removed_edges))
     }
     pub fn update_node_data(&mut self, id: &Id, data: N) -> N {
-        let Some((data_ptr, _)) = self.nodes.get_mut(id) else {
+        let Some((data_ptr, ..)) = self.nodes.get_mut(id) else {
             panic!("Can't update the data of a nonexistent node");
         };
         std::mem::replace(data_ptr, data)
     }
+    /// The removed edges are `(from, to)` for directed graphs, and start at the
+    /// removed node for undirected ones
     pub fn remove_node(&mut self, id: &Id) -> Option<(N, Vec<((Id, Id), E)>)> {
-        let (data, edges) = self.nodes.remove(id)?;
-        let removed_edges = self.remove_node_bits(*id, edges);
+        let (data, edges, in_edges) = self.nodes.remove(id)?;
+        let removed_edges = self.remove_node_bits(*id, edges, in_edges);
         Some((data, removed_edges))
     }
-    fn remove_node_bits(&mut self, id: Id, mut old_edges: HashMap<Id, usize>) -> Vec<((Id, Id), E)> {
+    fn remove_node_bits(
+        &mut self,
+        id: Id,
+        mut old_edges: HashMap<Id, usize>,
+        old_in_edges: HashMap<Id, usize>,
+    ) -> Vec<((Id, Id), E)> {
+        if D::DIRECTED {
+            return self.remove_directed_node_bits(id, old_edges, old_in_edges);
+        }
         let extra_removed_edge = if let Some(edge_data_id) = old_edges.remove(&id) {
             let data = self.edge_data
                 .remove(&edge_data_id)
@@ -50,6 +96,43 @@ impl<Id: Hash + Eq + Copy, N, E> Graph<Id, N, E> {
         removed_edges.extend(extra_removed_edge);
         removed_edges
     }
+    fn remove_directed_node_bits(
+        &mut self,
+        id: Id,
+        old_edges: HashMap<Id, usize>,
+        old_in_edges: HashMap<Id, usize>,
+    ) -> Vec<((Id, Id), E)> {
+        let mut removed_edges = Vec::with_capacity(old_edges.len() + old_in_edges.len());
+        for (other_id, edge_data_id) in old_edges {
+            if other_id != id {
+                self.nodes
+                    .get_mut(&other_id)
+                    .expect("Invariant violated: edge to a node that does not exist")
+                    .2
+                    .remove(&id)
+                    .expect("Invariant violated: nodes disagreed on the existence of an edge between them");
+            }
+            let data = self.edge_data
+                .remove(&edge_data_id)
+                .expect("Invariant violated: edge data ID missing associated data");
+            removed_edges.push(((id, other_id), data));
+        }
+        for (other_id, edge_data_id) in old_in_edges {
+            // Self-edges were removed along with the out-edges
+            if other_id == id { continue }
+            self.nodes
+                .get_mut(&other_id)
+                .expect("Invariant violated: edge from a node that does not exist")
+                .1
+                .remove(&id)
+                .expect("Invariant violated: nodes disagreed on the existence of an edge between them");
+            let data = self.edge_data
+                .remove(&edge_data_id)
+                .expect("Invariant violated: edge data ID missing associated data");
+            removed_edges.push(((other_id, id), data));
+        }
+        removed_edges
+    }
     /// Removes the edge from the list on the second node, and removes the edge data
     /// 
     /// Cannot remove self-edges, will cause function to panic
@@ -72,23 +155,44 @@ impl<Id: Hash + Eq + Copy, N, E> Graph<Id, N, E> {
         output
     }
     pub fn nodes(&self) -> impl Iterator<Item = (&Id, &N)> + '_ {
-        self.nodes.iter().map(|(id, (node_data, _))| (id, node_data))
+        self.nodes.iter().map(|(id, (node_data, ..))| (id, node_data))
     }
     pub fn get_node(&self, id: &Id) -> Option<&N> {
         self.nodes
             .get(&id)
-            .map(|(data, _)| data)
+            .map(|(data, ..)| data)
     }
     pub fn get_node_mut(&mut self, id: &Id) -> Option<&mut N> {
         self.nodes
             .get_mut(&id)
-            .map(|(data, _)| data)
+            .map(|(data, ..)| data)
     }
+    /// The out-edges for directed graphs
     pub fn edges_for<'a>(&'a self, id: &Id) -> impl Iterator<Item = (Id, &'a E)> + 'a {
-        self.nodes
+        let edges = &self.nodes
             .get(id)
             .expect("Cannot get the edges for a nonexistent node")
-            .1
+            .1;
+        self.with_edge_data(edges)
+    }
+    pub fn out_edges<'a>(&'a self, id: &Id) -> impl Iterator<Item = (Id, &'a E)> + 'a {
+        self.edges_for(id)
+    }
+    /// The same as `out_edges` for undirected graphs
+    pub fn in_edges<'a>(&'a self, id: &Id) -> impl Iterator<Item = (Id, &'a E)> + 'a {
+        let (_, edges, in_edges) = self.nodes
+            .get(id)
+            .expect("Cannot get the edges for a nonexistent node");
+        self.with_edge_data(if D::DIRECTED { in_edges } else { edges })
+    }
+    pub fn successors<'a>(&'a self, id: &Id) -> impl Iterator<Item = Id> + 'a {
+        self.out_edges(id).map(|(other_id, _)| other_id)
+    }
+    pub fn predecessors<'a>(&'a self, id: &Id) -> impl Iterator<Item = Id> + 'a {
+        self.in_edges(id).map(|(other_id, _)| other_id)
+    }
+    fn with_edge_data<'a>(&'a self, edges: &'a HashMap<Id, usize>) -> impl Iterator<Item = (Id, &'a E)> + 'a {
+        edges
             .iter()
             .map(|(&other_id, edge_data_id)| {
                 let edge_data = self.edge_data
@@ -97,8 +201,30 @@ impl<Id: Hash + Eq + Copy, N, E> Graph<Id, N, E> {
                 (other_id, edge_data)
             })
     }
+    /// Adds an edge from `a` to `b`, or replaces its data
     pub fn insert_edge(&mut self, a: &Id, b: &Id, edge_data: E) -> Option<E> {
-        if a == b {
+        if D::DIRECTED {
+            if !self.nodes.contains_key(b) {
+                panic!("Precondition violated: cannot add an edge between 2 nodes where at least 1 is nonexistent")
+            }
+            let (_, edge_map_a, _) = self.nodes
+                .get_mut(a)
+                .expect("Precondition violated: cannot add an edge between 2 nodes where at least 1 is nonexistent");
+            if let Some(edge_data_id) = edge_map_a.get(b) {
+                let prev_data = self.edge_data
+                    .insert(*edge_data_id, edge_data)
+                    .expect("Invariant violated: edge data ID missing associated data");
+                return Some(prev_data);
+            }
+            let new_edge_data_id = self.next_edge_data_id;
+            self.next_edge_data_id += 1;
+            if self.edge_data.insert(new_edge_data_id, edge_data).is_some() {
+                panic!("Invariant violated: there was an edge entry with the value of self.next_edge_data_id");
+            }
+            edge_map_a.insert(*b, new_edge_data_id);
+            self.nodes.get_mut(b).unwrap().2.insert(*a, new_edge_data_id);
+            None
+        } else if a == b {
             let edge_data_id = *self.nodes
                 .get_mut(a)
                 .expect("Precondition violated: cannot add self-edge on node that does not exist")
@@ -111,7 +237,7 @@ impl<Id: Hash + Eq + Copy, N, E> Graph<Id, N, E> {
                 });
             self.edge_data.insert(edge_data_id, edge_data)
         } else {
-            let [Some((_, edge_map_a)), Some((_, edge_map_b))] = self.nodes.get_disjoint_mut([a, b]) else {
+            let [Some((_, edge_map_a, _)), Some((_, edge_map_b, _))] = self.nodes.get_disjoint_mut([a, b]) else {
                 panic!("Precondition violated: cannot add an edge between 2 nodes where at least 1 is nonexistent")
             };
             match (edge_map_a.get_mut(b), edge_map_b.get_mut(a)) {
@@ -144,11 +270,11 @@ impl<Id: Hash + Eq + Copy, N, E> Graph<Id, N, E> {
             .expect("Precondition violated: cannot remove edge on a node that does not exist")
             .1
             .remove(b)?;
-        if a != b {
-            let other_edge_data_id = self.nodes
+        if D::DIRECTED || a != b {
+            let (_, edge_map_b, in_edge_map_b) = self.nodes
                 .get_mut(b)
-                .expect("Precondition violated: cannot remove edge on a node that does not exist")
-                .1
+                .expect("Precondition violated: cannot remove edge on a node that does not exist");
+            let other_edge_data_id = if D::DIRECTED { in_edge_map_b } else { edge_map_b }
                 .remove(a)
                 .expect("Invariant violated: nodes disagreed on the existence of an edge between them");
             if edge_data_id != other_edge_data_id {
@@ -177,23 +303,83 @@ impl<Id: Hash + Eq + Copy, N, E> Graph<Id, N, E> {
 }
 
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum Distance<E> {
-    Min,
-    Val(E),
-    Infinity,
+/// What a search knows about a node it has reached
+#[derive(Debug, Clone)]
+pub struct Reached<Id, E> {
+    distance: E,
+    /// The node(s) it was reached from along a shortest path
+    predecessors: Vec<Id>,
+    settled: bool,
+}
+
+/// Where a search from `start` got to: each reached node's distance, and the
+/// node(s) it was reached from along a shortest path
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<Id, E, M = HashMap<Id, Reached<Id, E>>> {
+    start: Id,
+    reached: M,
+    /// In the order they were settled, so nearest first
+    settled: Vec<Id>,
+    edge: PhantomData<E>,
 }
-impl<E: Clone> Distance<E> where for<'a> &'a E: Add<Output = E> {
-    pub fn add(&self, e: &E) -> Self {
-        match self {
-            Self::Infinity => Self::Infinity,
-            Self::Min => Self::Val(e.clone()),
-            Self::Val(curr) => Self::Val(curr + e), 
+
+impl<Id: Hash + Eq + Copy, E, M: StateMap<Id, Reached<Id, E>>> ShortestPaths<Id, E, M> {
+    pub fn start(&self) -> Id {
+        self.start
+    }
+    /// Only settled nodes' distances are final
+    fn reached(&self, id: &Id) -> Option<&Reached<Id, E>> {
+        self.reached.get(id).filter(|reached| reached.settled)
+    }
+    fn predecessors(&self, id: &Id) -> &[Id] {
+        self.reached(id).map_or(&[], |reached| &reached.predecessors)
+    }
+    pub fn distance(&self, to: &Id) -> Option<&E> {
+        self.reached(to).map(|reached| &reached.distance)
+    }
+    /// Every node reached and its distance, nearest first
+    pub fn distances(&self) -> impl Iterator<Item = (Id, &E)> + '_ {
+        self.settled.iter().map(|id| (*id, self.distance(id).expect("Invariant violated: settled node not reached")))
+    }
+    /// A shortest path from `start` to `to`, including both
+    pub fn path_to(&self, to: &Id) -> Option<Vec<Id>> {
+        self.reached(to)?;
+        let mut path = vec![*to];
+        let mut curr = *to;
+        while curr != self.start {
+            curr = *self.predecessors(&curr)
+                .first()
+                .expect("Invariant violated: reached node has no predecessor");
+            path.push(curr);
+        }
+        path.reverse();
+        Some(path)
+    }
+    /// Every shortest path to `to`, if the search kept them all. There can be
+    /// exponentially many, see `nodes_on_paths_to`.
+    pub fn all_paths_to(&self, to: &Id) -> Vec<Vec<Id>> {
+        if self.reached(to).is_none() { return vec![] }
+        if *to == self.start { return vec![vec![self.start]] }
+        self.predecessors(to)
+            .iter()
+            .flat_map(|prev| self.all_paths_to(prev))
+            .map(|mut path| { path.push(*to); path })
+            .collect()
+    }
+    /// The nodes on any of the shortest paths to `to`
+    pub fn nodes_on_paths_to(&self, to: &Id) -> HashSet<Id> {
+        let mut nodes = HashSet::new();
+        if self.reached(to).is_none() { return nodes }
+        let mut stack = vec![*to];
+        while let Some(id) = stack.pop() {
+            if !nodes.insert(id) { continue }
+            stack.extend(self.predecessors(&id).iter().copied());
         }
+        nodes
     }
 }
 
-impl<Id: Hash + Eq + Copy + std::fmt::Debug, N, E: Ord + Clone + std::fmt::Debug> Graph<Id, N, E>
+impl<Id: Hash + Eq + Copy + std::fmt::Debug, N, E: Ord + Clone + std::fmt::Debug> Graph<Id, N, E, Undirected>
 where for<'a> &'a E: Add<Output = E> {
     pub fn reduce_chains(&mut self, mut keep: impl FnMut(Id) -> bool) -> Vec<N> {
         let mut to_remove = vec![];
@@ -215,71 +401,179 @@ where for<'a> &'a E: Add<Output = E> {
 
         removed
     }
-    /// May be incorrect on negative edges
-    pub fn dijkstra(&self, start: &Id, target: &Id) -> Option<(E, Vec<Id>)> {
-        // Set up visited and unvisited sets/lists
-        let mut visited = HashMap::<Id, Distance<E>>::new();
-
-        let mut unvisited: HashMap<Id, Distance<E>> = self.nodes().map(|(id, _)| (
-            *id,
-            if id == start { Distance::Min } else { Distance::Infinity },
-        )).collect();
-        let mut unvisited_rev: BTreeMap<Distance<E>, HashSet<Id>> = BTreeMap::new();
-        for (&id, distance) in unvisited.iter() {
-            unvisited_rev
-                .entry(distance.clone())
-                .or_insert_with(|| HashSet::with_capacity(1))
-                .insert(id);
+}
+
+/// Edge lengths the searches can add by reference, so they don't clone an edge
+/// on every step. `default()` must be a length of 0.
+pub trait Length: Ord + Clone + Default {
+    fn plus(&self, other: &Self) -> Self;
+}
+impl<E: Ord + Clone + Default> Length for E where for<'a> &'a E: Add<Output = E> {
+    fn plus(&self, other: &Self) -> Self {
+        self + other
+    }
+}
+
+/// The nodes numbered from 0, and each one's edges by number, from
+/// `GraphSearch::indexed`
+pub type Indexed<'a, Node, E> = (Vec<Node>, Vec<Vec<(usize, &'a E)>>);
+
+/// The shortest path searches, shared by `Graph` and `DenseGraph` so code can
+/// switch between them. Edges must not be negative. See `GraphAlgorithms` for
+/// the rest.
+pub trait GraphSearch {
+    type Node: Hash + Eq + Copy;
+    type Edge;
+    type Direction: Direction;
+    /// Where searches record the nodes they reach: a `HashMap` for `Graph`, and
+    /// a `DenseMap` for `DenseGraph` so it skips hashing
+    type ReachedMap: StateMap<Self::Node, Reached<Self::Node, Self::Edge>>;
+
+    fn node_ids(&self) -> impl Iterator<Item = Self::Node> + '_;
+    /// The out-edges for directed graphs
+    fn edges_from(&self, node: &Self::Node) -> impl Iterator<Item = (Self::Node, &Self::Edge)> + '_;
+    /// An empty map for a search
+    fn reached_map(&self) -> Self::ReachedMap;
+
+    /// The nodes numbered from 0, and each one's edges (out-edges for directed
+    /// graphs) by number
+    fn indexed(&self) -> Indexed<'_, Self::Node, Self::Edge> {
+        let ids: Vec<Self::Node> = self.node_ids().collect();
+        let index: HashMap<Self::Node, usize> = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+        let edges = ids.iter()
+            .map(|id| self.edges_from(id).map(|(other_id, edge_data)| (index[&other_id], edge_data)).collect())
+            .collect();
+        (ids, edges)
+    }
+
+    fn dijkstra(&self, start: &Self::Node, target: &Self::Node) -> Option<(Self::Edge, Vec<Self::Node>)>
+    where Self::Edge: Length {
+        self.astar(start, target, |_| Self::Edge::default())
+    }
+    /// `heuristic` estimates the distance left to `goal`. It must never drop by
+    /// more than the length of the edge taken, so it never overestimates.
+    fn astar(
+        &self,
+        start: &Self::Node,
+        goal: &Self::Node,
+        heuristic: impl FnMut(&Self::Node) -> Self::Edge,
+    ) -> Option<(Self::Edge, Vec<Self::Node>)>
+    where Self::Edge: Length {
+        let paths = search::<Self, false>(self, start, &[*goal], heuristic);
+        Some((paths.distance(goal)?.clone(), paths.path_to(goal)?))
+    }
+    /// The distance to every node reachable from `start`
+    fn dijkstra_all(&self, start: &Self::Node) -> ShortestPaths<Self::Node, Self::Edge, Self::ReachedMap>
+    where Self::Edge: Length {
+        search::<Self, false>(self, start, &[], |_| Self::Edge::default())
+    }
+    /// Stops once all of `targets` have been reached, or carries on through the
+    /// whole graph if there are none
+    fn dijkstra_multi(
+        &self,
+        start: &Self::Node,
+        targets: &[Self::Node],
+    ) -> ShortestPaths<Self::Node, Self::Edge, Self::ReachedMap>
+    where Self::Edge: Length {
+        search::<Self, false>(self, start, targets, |_| Self::Edge::default())
+    }
+    /// `dijkstra_multi`, but keeping every shortest path rather than one per node
+    fn all_shortest_paths(
+        &self,
+        start: &Self::Node,
+        targets: &[Self::Node],
+    ) -> ShortestPaths<Self::Node, Self::Edge, Self::ReachedMap>
+    where Self::Edge: Length {
+        search::<Self, true>(self, start, targets, |_| Self::Edge::default())
+    }
+}
+
+impl<Id: Hash + Eq + Copy, N, E, D: Direction> GraphSearch for Graph<Id, N, E, D> {
+    type Node = Id;
+    type Edge = E;
+    type Direction = D;
+    type ReachedMap = HashMap<Id, Reached<Id, E>>;
+
+    fn node_ids(&self) -> impl Iterator<Item = Id> + '_ {
+        self.nodes.keys().copied()
+    }
+    fn edges_from(&self, node: &Id) -> impl Iterator<Item = (Id, &E)> + '_ {
+        self.edges_for(node)
+    }
+    fn reached_map(&self) -> Self::ReachedMap {
+        HashMap::new()
+    }
+}
+
+fn search<G: GraphSearch + ?Sized, const ALL_PATHS: bool>(
+    graph: &G,
+    start: &G::Node,
+    targets: &[G::Node],
+    mut heuristic: impl FnMut(&G::Node) -> G::Edge,
+) -> ShortestPaths<G::Node, G::Edge, G::ReachedMap>
+where G::Edge: Length {
+    let mut reached = graph.reached_map();
+    reached.insert(*start, Reached { distance: G::Edge::default(), predecessors: vec![], settled: false });
+    let mut settled = vec![];
+    let mut targets_left = targets.to_vec();
+    // Once every target is settled, the priority past which nothing else is
+    // on a shortest path to them
+    let mut done_at = None;
+
+    let mut heap = BinaryHeap::from([Queued { priority: heuristic(start), distance: G::Edge::default(), id: *start }]);
+    while let Some(Queued { priority, distance, id }) = heap.pop() {
+        if done_at.as_ref().is_some_and(|done_at| &priority > done_at) { break }
+        let node = reached.get_mut(&id).expect("Invariant violated: queued node was never reached");
+        if node.settled { continue }
+        node.settled = true;
+        settled.push(id);
+        let targets_before = targets_left.len();
+        targets_left.retain(|target| *target != id);
+        if targets_left.len() < targets_before && targets_left.is_empty() {
+            if !ALL_PATHS { break }
+            done_at = Some(priority);
         }
 
-        'outer: while let Some((distance, hash)) = unvisited_rev.pop_first() {
-            if distance == Distance::Infinity { return None; }
-            for &id_to_process in &hash {
-                visited.insert(id_to_process, distance.clone());
-                unvisited.remove(&id_to_process);
-            }
-            for id_to_process in hash {
-                if &id_to_process == target { break 'outer }
-
-                for (neighbor_id, edge_data) in self.neighbors(&id_to_process) {
-                    let Some(curr_neighbor_dist) = unvisited.get_mut(&neighbor_id) else { continue };
-                    let new_dist = distance.add(edge_data);
-                    if &new_dist < &*curr_neighbor_dist {
-                        unvisited_rev
-                            .get_mut(&curr_neighbor_dist)
-                            .expect("Invariant violated: unvisited_rev map is out of sync with unvisited map")
-                            .remove(&neighbor_id);
-                        unvisited_rev
-                            .entry(new_dist.clone())
-                            .or_insert_with(|| HashSet::with_capacity(1))
-                            .insert(neighbor_id);
-                        *curr_neighbor_dist = new_dist;
-                    }
-                }
+        for (neighbor_id, edge_data) in graph.edges_from(&id) {
+            let new_dist = distance.plus(edge_data);
+            let known = reached.get(&neighbor_id).map(|neighbor| (neighbor.settled, new_dist.cmp(&neighbor.distance)));
+            match known {
+                Some((true, _)) if !ALL_PATHS => {},
+                Some((_, Ordering::Greater)) => {},
+                Some((neighbor_settled, Ordering::Equal)) => if ALL_PATHS {
+                    // Zero length edges can still lead to settled nodes along
+                    // another shortest path, unless it would make a loop
+                    if neighbor_settled && leads_to(&reached, neighbor_id, id) { continue }
+                    reached.get_mut(&neighbor_id)
+                        .expect("Invariant violated: reached node missing")
+                        .predecessors
+                        .push(id);
+                },
+                _ => {
+                    let priority = new_dist.plus(&heuristic(&neighbor_id));
+                    reached.insert(neighbor_id, Reached { distance: new_dist.clone(), predecessors: vec![id], settled: false });
+                    heap.push(Queued { priority, distance: new_dist, id: neighbor_id });
+                },
             }
         }
-        let Some(output) = visited.remove(target) else { return None; };
-        let mut path = vec![];
-        let mut curr = *target;
-        let mut curr_dist = output.clone();
-        while &curr != start {
-            path.push(curr);
-            let (prev, prev_dist) = self.edges_for(&curr)
-                .flat_map(|(id, _)| Some((id, visited.remove(&id)?)))
-                .find(|(_, new_dist)| new_dist < &curr_dist)
-                .expect("Something went wrong!");
-            curr = prev;
-            curr_dist = prev_dist;
-        }
-        path.push(*start);
-        path.reverse();
+    }
 
-        let Distance::Val(output) = output else { todo!() };
-        Some((output, path))
+    ShortestPaths { start: *start, reached, settled, edge: PhantomData }
+}
+
+/// Whether `from` is on a shortest path to `to` found so far
+fn leads_to<Id: Hash + Eq + Copy, E>(reached: &impl StateMap<Id, Reached<Id, E>>, from: Id, to: Id) -> bool {
+    let mut seen = HashSet::new();
+    let mut stack = vec![to];
+    while let Some(id) = stack.pop() {
+        if id == from { return true }
+        if !seen.insert(id) { continue }
+        stack.extend(reached.get(&id).into_iter().flat_map(|reached| reached.predecessors.iter().copied()));
     }
+    false
 }
 
-impl<Id: Hash + Eq + Copy, N, E: Ord> Graph<Id, N, E> {
+impl<Id: Hash + Eq + Copy, N, E: Ord, D: Direction> Graph<Id, N, E, D> {
     pub fn neighbors(&self, of_node: &Id) -> impl Iterator<Item = (Id, &E)> + '_ {
         self.edges_for(of_node)
     }
@@ -306,3 +600,106 @@ fn test_dijkstra() {
 
     assert_eq!(graph.dijkstra(&b'A', &b'C'), Some((3, vec![b'A', b'E', b'C'])));
 }
+
+#[test]
+fn test_directed() {
+    let mut graph = DiGraph::<u8, (), usize>::new();
+    for id in b'A'..=b'D' {
+        graph.insert_node(id, ());
+    }
+    graph.insert_edge(&b'A', &b'B', 1);
+    graph.insert_edge(&b'B', &b'C', 1);
+    graph.insert_edge(&b'A', &b'C', 5);
+    graph.insert_edge(&b'D', &b'A', 1);
+    graph.insert_edge(&b'C', &b'C', 1);
+
+    let mut successors: Vec<_> = graph.successors(&b'A').collect();
+    successors.sort();
+    assert_eq!(successors, vec![b'B', b'C']);
+    let mut predecessors: Vec<_> = graph.predecessors(&b'C').collect();
+    predecessors.sort();
+    assert_eq!(predecessors, vec![b'A', b'B', b'C']);
+
+    assert_eq!(graph.dijkstra(&b'D', &b'C'), Some((3, vec![b'D', b'A', b'B', b'C'])));
+    assert_eq!(graph.dijkstra(&b'C', &b'A'), None);
+
+    assert_eq!(graph.remove_edge(&b'B', &b'A'), None);
+    assert_eq!(graph.remove_edge(&b'A', &b'B'), Some(1));
+    assert_eq!(graph.predecessors(&b'B').count(), 0);
+
+    let (_, mut removed) = graph.remove_node(&b'C').unwrap();
+    removed.sort();
+    assert_eq!(removed, vec![((b'A', b'C'), 5), ((b'B', b'C'), 1), ((b'C', b'C'), 1)]);
+    assert_eq!(graph.successors(&b'A').count(), 0);
+}
+
+#[test]
+fn test_shortest_paths() {
+    // A 3x3 grid of unit edges, with the middle removed
+    let mut graph = Graph::<(u8, u8), (), u32>::new();
+    let cells: Vec<_> = (0..3).flat_map(|y| (0..3).map(move |x| (x, y))).filter(|&c| c != (1, 1)).collect();
+    for &cell in &cells {
+        graph.insert_node(cell, ());
+    }
+    for &(x, y) in &cells {
+        for other in [(x + 1, y), (x, y + 1)] {
+            if graph.get_node(&other).is_some() {
+                graph.insert_edge(&(x, y), &other, 1);
+            }
+        }
+    }
+
+    let manhattan = |&(x, y): &(u8, u8)| (2 - x + 2 - y) as u32;
+    assert_eq!(graph.astar(&(0, 0), &(2, 2), manhattan).map(|(distance, path)| (distance, path.len())), Some((4, 5)));
+    assert_eq!(graph.dijkstra(&(0, 0), &(0, 0)), Some((0, vec![(0, 0)])));
+
+    let all = graph.dijkstra_all(&(0, 0));
+    assert_eq!(all.distances().count(), 8);
+    assert_eq!(all.distance(&(2, 1)), Some(&3));
+    assert!(all.distances().map(|(_, distance)| *distance).is_sorted());
+
+    let paths = graph.dijkstra_multi(&(0, 0), &[(1, 0), (0, 2)]);
+    assert_eq!(paths.distance(&(0, 2)), Some(&2));
+    assert_eq!(paths.distance(&(2, 2)), None);
+
+    let paths = graph.all_shortest_paths(&(0, 0), &[(2, 2)]);
+    let mut all_paths = paths.all_paths_to(&(2, 2));
+    all_paths.sort();
+    assert_eq!(all_paths, vec![
+        vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)],
+        vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+    ]);
+    assert_eq!(paths.nodes_on_paths_to(&(2, 2)).len(), 8);
+}
+
+#[test]
+fn test_zero_length_shortest_paths() {
+    // Y is settled before X, so the second path to D only appears through the
+    // zero length edge X -> Y into an already settled node
+    let mut graph = DiGraph::<char, (), u32>::new();
+    for id in ['A', 'Q', 'X', 'Y', 'D'] {
+        graph.insert_node(id, ());
+    }
+    for (a, b, length) in [('A', 'Y', 1), ('A', 'Q', 0), ('Q', 'X', 1), ('X', 'Y', 0), ('Y', 'D', 1)] {
+        graph.insert_edge(&a, &b, length);
+    }
+    let mut all_paths: Vec<String> = graph.all_shortest_paths(&'A', &['D'])
+        .all_paths_to(&'D')
+        .into_iter()
+        .map(String::from_iter)
+        .collect();
+    all_paths.sort();
+    assert_eq!(all_paths, vec!["AQXYD", "AYD"]);
+
+    // Both ways along a zero length edge mustn't make paths that loop forever
+    let mut graph = Graph::<char, (), u32>::new();
+    for id in ['A', 'Q', 'X', 'Y', 'D'] {
+        graph.insert_node(id, ());
+    }
+    for (a, b, length) in [('A', 'Y', 1), ('A', 'Q', 0), ('Q', 'X', 1), ('X', 'Y', 0), ('Y', 'D', 1)] {
+        graph.insert_edge(&a, &b, length);
+    }
+    let paths = graph.all_shortest_paths(&'A', &[]);
+    assert_eq!(paths.distance(&'D'), Some(&2));
+    assert_eq!(paths.all_paths_to(&'X').len() + paths.all_paths_to(&'Y').len(), 3);
+}
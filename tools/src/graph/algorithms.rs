@@ -0,0 +1,357 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+use super::{routes, Directed, Direction, Distances, GraphSearch, Length, Undirected};
+
+/// Algorithms for any `GraphSearch`, so `Graph` and `DenseGraph` alike. They
+/// number the nodes with `GraphSearch::indexed` and work on the numbers.
+pub trait GraphAlgorithms: GraphSearch {
+    /// Groups of nodes joined by edges. Edge direction is ignored, so directed
+    /// graphs give their weakly connected components.
+    fn connected_components(&self) -> Vec<Vec<Self::Node>> {
+        let (ids, edges) = self.indexed();
+        let mut neighbors: Vec<Vec<usize>> = edges.iter()
+            .map(|edges| edges.iter().map(|&(other, _)| other).collect())
+            .collect();
+        if Self::Direction::DIRECTED {
+            for (i, edges) in edges.iter().enumerate() {
+                for &(other, _) in edges {
+                    neighbors[other].push(i);
+                }
+            }
+        }
+
+        let mut seen = vec![false; ids.len()];
+        let mut components = vec![];
+        for start in 0..ids.len() {
+            if seen[start] { continue }
+            seen[start] = true;
+            let mut component = vec![];
+            let mut queue = VecDeque::from([start]);
+            while let Some(i) = queue.pop_front() {
+                component.push(ids[i]);
+                for &other in &neighbors[i] {
+                    if !seen[other] {
+                        seen[other] = true;
+                        queue.push_back(other);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// Orders the nodes so every edge goes forwards. When several nodes are
+    /// ready, the smallest goes first. `None` if there's a cycle.
+    fn topological_sort(&self) -> Option<Vec<Self::Node>>
+    where Self: GraphSearch<Direction = Directed>, Self::Node: Ord {
+        let (ids, edges) = self.indexed();
+        let mut in_degrees = vec![0; ids.len()];
+        for &(other, _) in edges.iter().flatten() {
+            in_degrees[other] += 1;
+        }
+        let mut ready: BinaryHeap<Reverse<(Self::Node, usize)>> = (0..ids.len())
+            .filter(|&i| in_degrees[i] == 0)
+            .map(|i| Reverse((ids[i], i)))
+            .collect();
+
+        let mut order = Vec::with_capacity(ids.len());
+        while let Some(Reverse((id, i))) = ready.pop() {
+            order.push(id);
+            for &(next, _) in &edges[i] {
+                in_degrees[next] -= 1;
+                if in_degrees[next] == 0 {
+                    ready.push(Reverse((ids[next], next)));
+                }
+            }
+        }
+        (order.len() == ids.len()).then_some(order)
+    }
+
+    /// Every clique that can't be grown any further (Bron–Kerbosch with pivoting)
+    fn maximal_cliques(&self) -> Vec<Vec<Self::Node>>
+    where Self: GraphSearch<Direction = Undirected> {
+        let (ids, edges) = self.indexed();
+        let neighbors: Vec<HashSet<usize>> = without_self_edges(&edges)
+            .into_iter()
+            .map(|neighbors| neighbors.into_iter().collect())
+            .collect();
+        let mut cliques = vec![];
+        bron_kerbosch(&neighbors, &mut vec![], (0..ids.len()).collect(), HashSet::new(), &mut |clique| {
+            cliques.push(clique.iter().map(|&i| ids[i]).collect());
+        });
+        cliques
+    }
+    /// One of the largest cliques
+    fn max_clique(&self) -> Vec<Self::Node>
+    where Self: GraphSearch<Direction = Undirected> {
+        self.maximal_cliques()
+            .into_iter()
+            .max_by_key(Vec::len)
+            .unwrap_or_default()
+    }
+
+    /// The lightest set of edges that splits the graph in two (Stoer–Wagner).
+    /// Gives the cut's total weight and the nodes on one side, or `None` if
+    /// there are fewer than 2 nodes.
+    fn min_cut(&self, mut weight: impl FnMut(&Self::Edge) -> u64) -> Option<(u64, Vec<Self::Node>)>
+    where Self: GraphSearch<Direction = Undirected> {
+        let mut components = self.connected_components();
+        if components.len() > 1 {
+            return Some((0, components.swap_remove(0)));
+        }
+        let (ids, edges) = self.indexed();
+        if ids.len() < 2 { return None }
+
+        let mut edges: Vec<HashMap<usize, u64>> = edges.iter()
+            .enumerate()
+            .map(|(i, edges)| edges.iter()
+                .filter(|&&(other, _)| other != i)
+                .map(|&(other, edge_data)| (other, weight(edge_data)))
+                .collect())
+            .collect();
+        // The original nodes each remaining node stands for
+        let mut members: Vec<Vec<usize>> = (0..ids.len()).map(|i| vec![i]).collect();
+        let mut merged = vec![false; ids.len()];
+
+        let mut best: Option<(u64, Vec<usize>)> = None;
+        for _ in 1..ids.len() {
+            // Add the most tightly connected node until all are added. The cut
+            // between the last one and the rest is a candidate.
+            let start = (0..ids.len()).find(|&i| !merged[i]).unwrap();
+            let mut added = vec![false; ids.len()];
+            let mut connection = vec![0; ids.len()];
+            let mut heap = BinaryHeap::from([(0, start)]);
+            let (mut second_last, mut last, mut cut) = (start, start, 0);
+            while let Some((connected, i)) = heap.pop() {
+                if added[i] || connected != connection[i] { continue }
+                added[i] = true;
+                (second_last, last, cut) = (last, i, connected);
+                for (&other, &edge_weight) in &edges[i] {
+                    if added[other] { continue }
+                    connection[other] += edge_weight;
+                    heap.push((connection[other], other));
+                }
+            }
+            if best.as_ref().is_none_or(|(best_cut, _)| cut < *best_cut) {
+                best = Some((cut, members[last].clone()));
+            }
+
+            // Merge the last node into the second last
+            merged[last] = true;
+            let last_members = std::mem::take(&mut members[last]);
+            members[second_last].extend(last_members);
+            for (other, edge_weight) in std::mem::take(&mut edges[last]) {
+                edges[other].remove(&last);
+                if other == second_last { continue }
+                *edges[second_last].entry(other).or_default() += edge_weight;
+                *edges[other].entry(second_last).or_default() += edge_weight;
+            }
+        }
+
+        best.map(|(cut, side)| (cut, side.into_iter().map(|i| ids[i]).collect()))
+    }
+
+    /// Edges whose removal would disconnect their nodes
+    fn bridges(&self) -> Vec<(Self::Node, Self::Node)>
+    where Self: GraphSearch<Direction = Undirected> {
+        let (ids, edges) = self.indexed();
+        let (bridges, _) = low_links(&without_self_edges(&edges));
+        bridges.into_iter().map(|(a, b)| (ids[a], ids[b])).collect()
+    }
+    /// Nodes whose removal would split up the rest of their component
+    fn articulation_points(&self) -> Vec<Self::Node>
+    where Self: GraphSearch<Direction = Undirected> {
+        let (ids, edges) = self.indexed();
+        let (_, articulation_points) = low_links(&without_self_edges(&edges));
+        articulation_points.into_iter().map(|i| ids[i]).collect()
+    }
+
+    /// Floyd–Warshall, so best kept to small graphs like those left by
+    /// `reduce_chains`
+    fn floyd_warshall(&self) -> Distances<Self::Node, Self::Edge>
+    where Self::Edge: Length {
+        let (ids, edges) = self.indexed();
+        routes::floyd_warshall(ids, &edges)
+    }
+    /// The longest path from `start` to `end` that doesn't revisit a node, e.g. on
+    /// a maze after `reduce_chains`. Searches every path, skipping those that
+    /// can't beat the best so far even if they went on to visit every node left.
+    fn longest_path(&self, start: &Self::Node, end: &Self::Node) -> Option<Self::Edge>
+    where Self::Edge: Length {
+        let (ids, edges) = self.indexed();
+        let start = ids.iter().position(|id| id == start)?;
+        let end = ids.iter().position(|id| id == end)?;
+        routes::longest_path(&without_self_edges_data(&edges), start, end)
+    }
+}
+
+impl<G: GraphSearch + ?Sized> GraphAlgorithms for G {}
+
+/// Each node's neighbours by number, leaving out self-edges
+fn without_self_edges<E>(edges: &[Vec<(usize, &E)>]) -> Vec<Vec<usize>> {
+    edges.iter()
+        .enumerate()
+        .map(|(i, edges)| edges.iter().map(|&(other, _)| other).filter(|&other| other != i).collect())
+        .collect()
+}
+/// `without_self_edges`, keeping the edge data
+fn without_self_edges_data<E: Clone>(edges: &[Vec<(usize, &E)>]) -> Vec<Vec<(usize, E)>> {
+    edges.iter()
+        .enumerate()
+        .map(|(i, edges)| edges.iter()
+            .filter(|&&(other, _)| other != i)
+            .map(|&(other, edge_data)| (other, edge_data.clone()))
+            .collect())
+        .collect()
+}
+
+/// Tarjan's bridge and articulation point search, with an explicit stack as
+/// mazes can be deep
+fn low_links(neighbors: &[Vec<usize>]) -> (Vec<(usize, usize)>, Vec<usize>) {
+    const UNSEEN: usize = usize::MAX;
+    let n = neighbors.len();
+    let mut discovered = vec![UNSEEN; n];
+    let mut low = vec![UNSEEN; n];
+    let mut is_articulation = vec![false; n];
+    let mut bridges = vec![];
+    let mut time = 0;
+
+    for root in 0..n {
+        if discovered[root] != UNSEEN { continue }
+        discovered[root] = time;
+        low[root] = time;
+        time += 1;
+        let mut root_children = 0;
+        // Node, parent and the index of the next neighbour to look at
+        let mut stack = vec![(root, UNSEEN, 0)];
+        while let Some(top) = stack.last_mut() {
+            let (i, parent) = (top.0, top.1);
+            if let Some(&next) = neighbors[i].get(top.2) {
+                top.2 += 1;
+                if next == parent { continue }
+                if discovered[next] == UNSEEN {
+                    discovered[next] = time;
+                    low[next] = time;
+                    time += 1;
+                    if i == root { root_children += 1 }
+                    stack.push((next, i, 0));
+                } else {
+                    low[i] = low[i].min(discovered[next]);
+                }
+            } else {
+                stack.pop();
+                if parent == UNSEEN { continue }
+                low[parent] = low[parent].min(low[i]);
+                if low[i] > discovered[parent] {
+                    bridges.push((parent, i));
+                }
+                if parent != root && low[i] >= discovered[parent] {
+                    is_articulation[parent] = true;
+                }
+            }
+        }
+        if root_children > 1 {
+            is_articulation[root] = true;
+        }
+    }
+
+    (bridges, (0..n).filter(|&i| is_articulation[i]).collect())
+}
+
+fn bron_kerbosch(
+    neighbors: &[HashSet<usize>],
+    clique: &mut Vec<usize>,
+    mut candidates: HashSet<usize>,
+    mut excluded: HashSet<usize>,
+    report: &mut impl FnMut(&[usize]),
+) {
+    let Some(&pivot) = candidates.iter()
+        .chain(&excluded)
+        .max_by_key(|&&i| neighbors[i].intersection(&candidates).count())
+    else {
+        report(clique);
+        return;
+    };
+    let to_try: Vec<usize> = candidates.difference(&neighbors[pivot]).copied().collect();
+    for i in to_try {
+        clique.push(i);
+        bron_kerbosch(
+            neighbors,
+            clique,
+            candidates.intersection(&neighbors[i]).copied().collect(),
+            excluded.intersection(&neighbors[i]).copied().collect(),
+            report,
+        );
+        clique.pop();
+        candidates.remove(&i);
+        excluded.insert(i);
+    }
+}
+
+#[test]
+fn test_algorithms() {
+    use super::{DenseGraph, Graph};
+
+    // Two triangles joined by the bridge C-D, plus an isolated Z
+    let mut graph = Graph::<u8, (), ()>::new();
+    for &id in b"ABCDEFZ" {
+        graph.insert_node(id, ());
+    }
+    for (a, b) in [(b'A', b'B'), (b'B', b'C'), (b'C', b'A'), (b'C', b'D'), (b'D', b'E'), (b'E', b'F'), (b'F', b'D')] {
+        graph.insert_edge(&a, &b, ());
+    }
+
+    let mut components: Vec<_> = graph.connected_components().into_iter().map(|c| c.len()).collect();
+    components.sort();
+    assert_eq!(components, vec![1, 6]);
+
+    let mut max_clique = graph.max_clique();
+    max_clique.sort();
+    assert!(max_clique == vec![b'A', b'B', b'C'] || max_clique == vec![b'D', b'E', b'F']);
+    assert_eq!(graph.maximal_cliques().len(), 4);
+
+    let bridges = graph.bridges();
+    assert!(bridges == vec![(b'C', b'D')] || bridges == vec![(b'D', b'C')]);
+    let mut articulation_points = graph.articulation_points();
+    articulation_points.sort();
+    assert_eq!(articulation_points, vec![b'C', b'D']);
+
+    assert_eq!(graph.min_cut(|_| 1).map(|(cut, _)| cut), Some(0));
+    graph.remove_node(&b'Z');
+    let (cut, mut side) = graph.min_cut(|_| 1).unwrap();
+    side.sort();
+    assert_eq!(cut, 1);
+    assert!(side == vec![b'A', b'B', b'C'] || side == vec![b'D', b'E', b'F']);
+
+    // The same on a DenseGraph, whose nodes are numbers
+    let dense = DenseGraph::from(&graph);
+    let ids = |nodes: Vec<u32>| {
+        let mut ids: Vec<u8> = nodes.into_iter().map(|node| dense.id(node)).collect();
+        ids.sort();
+        ids
+    };
+    assert_eq!(dense.connected_components().len(), 1);
+    assert_eq!(ids(dense.articulation_points()), articulation_points);
+    assert_eq!(dense.bridges().len(), 1);
+    assert_eq!(dense.maximal_cliques().len(), 3);
+    assert_eq!(dense.min_cut(|_| 1).map(|(cut, side)| (cut, ids(side).len())), Some((1, 3)));
+
+    let mut steps = Graph::<char, (), (), Directed>::new();
+    for id in ['A', 'B', 'C', 'D', 'E', 'F'] {
+        steps.insert_node(id, ());
+    }
+    for (a, b) in [('C', 'A'), ('C', 'F'), ('A', 'B'), ('A', 'D'), ('B', 'E'), ('D', 'E'), ('F', 'E')] {
+        steps.insert_edge(&a, &b, ());
+    }
+    assert_eq!(steps.topological_sort().map(String::from_iter), Some("CABDFE".to_string()));
+    // Ties are broken by node number rather than ID, but every edge still goes forwards
+    let dense = DenseGraph::from(&steps);
+    let order = dense.topological_sort().unwrap();
+    let position = |id| order.iter().position(|&node| dense.id(node) == id).unwrap();
+    assert!(steps.nodes().all(|(&id, _)| steps.successors(&id).all(|next| position(id) < position(next))));
+    steps.insert_edge(&'E', &'C', ());
+    assert_eq!(steps.topological_sort(), None);
+    assert_eq!(DenseGraph::from(&steps).topological_sort(), None);
+}
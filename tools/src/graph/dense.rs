@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use super::{Direction, Graph, GraphSearch, Indexed, Reached, Undirected};
+use crate::search::DenseMap;
+
+/// A frozen copy of a `Graph` for searches on the hot path. Nodes are numbered
+/// from 0 and each node's edges are stored next to each other (compressed
+/// sparse rows). Searches keep what they know about each node in a `Vec`
+/// rather than a `HashMap`, and `GraphAlgorithms` don't need to number the
+/// nodes first. Nodes are the `u32` numbers, see `index_of` and `id`.
+#[derive(Debug, Clone)]
+pub struct DenseGraph<Id: Hash + Eq + Copy, E, D: Direction = Undirected> {
+    ids: Vec<Id>,
+    index: HashMap<Id, u32>,
+    /// Node `i`'s edges are `targets[offsets[i]..offsets[i + 1]]`
+    offsets: Vec<u32>,
+    targets: Vec<u32>,
+    edge_data: Vec<E>,
+    direction: PhantomData<D>,
+}
+
+impl<Id: Hash + Eq + Copy, E, D: Direction> DenseGraph<Id, E, D> {
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+    pub fn index_of(&self, id: &Id) -> Option<u32> {
+        self.index.get(id).copied()
+    }
+    pub fn id(&self, index: u32) -> Id {
+        self.ids[index as usize]
+    }
+    /// The out-edges for graphs made from directed graphs
+    pub fn edges(&self, index: u32) -> impl Iterator<Item = (u32, &E)> + '_ {
+        let range = self.offsets[index as usize] as usize..self.offsets[index as usize + 1] as usize;
+        self.targets[range.clone()].iter().copied().zip(&self.edge_data[range])
+    }
+}
+
+impl<Id: Hash + Eq + Copy, N, E: Clone, D: Direction> From<&Graph<Id, N, E, D>> for DenseGraph<Id, E, D> {
+    fn from(graph: &Graph<Id, N, E, D>) -> Self {
+        let ids: Vec<Id> = graph.nodes.keys().copied().collect();
+        assert!(ids.len() <= u32::MAX as usize, "Too many nodes to number with a u32");
+        let index: HashMap<Id, u32> = ids.iter().enumerate().map(|(i, &id)| (id, i as u32)).collect();
+
+        let mut offsets = Vec::with_capacity(ids.len() + 1);
+        let mut targets = vec![];
+        let mut edge_data = vec![];
+        offsets.push(0);
+        for id in &ids {
+            for (other_id, data) in graph.edges_for(id) {
+                targets.push(index[&other_id]);
+                edge_data.push(data.clone());
+            }
+            offsets.push(targets.len() as u32);
+        }
+
+        Self { ids, index, offsets, targets, edge_data, direction: PhantomData }
+    }
+}
+
+impl<Id: Hash + Eq + Copy, E, D: Direction> GraphSearch for DenseGraph<Id, E, D> {
+    type Node = u32;
+    type Edge = E;
+    type Direction = D;
+    type ReachedMap = DenseMap<Reached<u32, E>, fn(&u32) -> usize>;
+
+    fn node_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        0..self.len() as u32
+    }
+    fn edges_from(&self, node: &u32) -> impl Iterator<Item = (u32, &E)> + '_ {
+        self.edges(*node)
+    }
+    fn reached_map(&self) -> Self::ReachedMap {
+        DenseMap::new(self.len(), |&node| node as usize)
+    }
+    /// Already numbered, so no need to hash anything
+    fn indexed(&self) -> Indexed<'_, u32, E> {
+        let edges = self.node_ids()
+            .map(|node| self.edges(node).map(|(other, edge_data)| (other as usize, edge_data)).collect())
+            .collect();
+        (self.node_ids().collect(), edges)
+    }
+}
+
+#[test]
+fn test_dense_graph() {
+    let mut graph = Graph::<u8, (), usize>::new();
+    for &id in b"ABCDE" {
+        graph.insert_node(id, ());
+    }
+    graph.insert_edge(&b'A', &b'B', 7);
+    graph.insert_edge(&b'A', &b'E', 1);
+    graph.insert_edge(&b'B', &b'C', 3);
+    graph.insert_edge(&b'C', &b'E', 2);
+
+    let dense = DenseGraph::from(&graph);
+    assert_eq!(dense.len(), 5);
+    let a = dense.index_of(&b'A').unwrap();
+    let c = dense.index_of(&b'C').unwrap();
+    let (distance, path) = dense.dijkstra(&a, &c).unwrap();
+    assert_eq!(distance, 3);
+    assert_eq!(path.into_iter().map(|i| dense.id(i)).collect::<Vec<_>>(), vec![b'A', b'E', b'C']);
+    assert_eq!(dense.edges(dense.index_of(&b'D').unwrap()).count(), 0);
+    assert_eq!(dense.dijkstra_all(&a).distances().count(), graph.dijkstra_all(&b'A').distances().count());
+
+    let paths = dense.all_shortest_paths(&a, &[]);
+    assert_eq!(paths.all_paths_to(&c).len(), 1);
+    assert_eq!(paths.distance(&dense.index_of(&b'B').unwrap()), Some(&6));
+    assert_eq!(paths.distance(&dense.index_of(&b'D').unwrap()), None);
+}
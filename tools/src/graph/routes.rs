@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::Length;
+
+/// The shortest distance between every pair of nodes, from `GraphAlgorithms::floyd_warshall`
+#[derive(Debug, Clone)]
+pub struct Distances<Id: Hash + Eq + Copy, E> {
+    ids: Vec<Id>,
+    index: HashMap<Id, usize>,
+    distances: Vec<Option<E>>,
+}
+
+/// The cheapest way to visit each subset of some targets, from `Distances::visiting_subsets`.
+/// Subsets are bitmasks, with bit `i` for `targets[i]`.
+#[derive(Debug, Clone)]
+pub struct SubsetCosts<E> {
+    targets: usize,
+    costs: Vec<Option<E>>,
+}
+
+/// `GraphAlgorithms::floyd_warshall` on the numbered nodes. Edges must not be
+/// negative.
+pub(super) fn floyd_warshall<Id: Hash + Eq + Copy, E: Length>(ids: Vec<Id>, edges: &[Vec<(usize, &E)>]) -> Distances<Id, E> {
+    let n = ids.len();
+    let mut distances = vec![None; n * n];
+    for (i, edges) in edges.iter().enumerate() {
+        distances[i * n + i] = Some(E::default());
+        for &(other, edge_data) in edges {
+            let distance = &mut distances[i * n + other];
+            if distance.as_ref().is_none_or(|distance| edge_data < distance) {
+                *distance = Some(edge_data.clone());
+            }
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            let Some(to_k) = distances[i * n + k].clone() else { continue };
+            for j in 0..n {
+                let Some(from_k) = &distances[k * n + j] else { continue };
+                let through_k = to_k.plus(from_k);
+                if distances[i * n + j].as_ref().is_none_or(|distance| &through_k < distance) {
+                    distances[i * n + j] = Some(through_k);
+                }
+            }
+        }
+    }
+
+    let index = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+    Distances { ids, index, distances }
+}
+
+/// `GraphAlgorithms::longest_path` on the numbered nodes, without self-edges
+pub(super) fn longest_path<E: Length>(neighbors: &[Vec<(usize, E)>], start: usize, end: usize) -> Option<E> {
+    let mut longest_in: Vec<Option<E>> = vec![None; neighbors.len()];
+    let mut into_end = vec![];
+    for (i, edges) in neighbors.iter().enumerate() {
+        for (other, edge_data) in edges {
+            if longest_in[*other].as_ref().is_none_or(|longest| edge_data > longest) {
+                longest_in[*other] = Some(edge_data.clone());
+            }
+            if *other == end {
+                into_end.push(i);
+            }
+        }
+    }
+    // If only one node leads to the end, there's no point going anywhere else from it
+    let funnel = match into_end[..] {
+        [only] => Some(only),
+        _ => None,
+    };
+
+    let search = LongestPathSearch { neighbors, longest_in, end, funnel };
+    let mut visited = vec![false; neighbors.len()];
+    visited[start] = true;
+    let mut best = None;
+    search.search(start, &E::default(), &mut visited, &mut best);
+    best
+}
+
+struct LongestPathSearch<'a, E> {
+    neighbors: &'a [Vec<(usize, E)>],
+    /// The longest edge into each node
+    longest_in: Vec<Option<E>>,
+    end: usize,
+    funnel: Option<usize>,
+}
+
+impl<E: Length> LongestPathSearch<'_, E> {
+    fn search(&self, at: usize, length: &E, visited: &mut [bool], best: &mut Option<E>) {
+        if at == self.end {
+            if best.as_ref().is_none_or(|best| length > best) {
+                *best = Some(length.clone());
+            }
+            return;
+        }
+        if let Some(best) = best {
+            let upper_bound = (0..visited.len())
+                .filter(|&i| !visited[i])
+                .flat_map(|i| &self.longest_in[i])
+                .fold(length.clone(), |bound, longest_in| bound.plus(longest_in));
+            if &upper_bound <= best { return }
+        }
+
+        for (next, edge_data) in &self.neighbors[at] {
+            if visited[*next] { continue }
+            if self.funnel == Some(at) && *next != self.end { continue }
+            visited[*next] = true;
+            self.search(*next, &length.plus(edge_data), visited, best);
+            visited[*next] = false;
+        }
+    }
+}
+
+impl<Id: Hash + Eq + Copy, E> Distances<Id, E> {
+    pub fn ids(&self) -> &[Id] {
+        &self.ids
+    }
+    pub fn get(&self, from: &Id, to: &Id) -> Option<&E> {
+        let n = self.ids.len();
+        self.distances[self.index.get(from)? * n + self.index.get(to)?].as_ref()
+    }
+}
+
+impl<Id: Hash + Eq + Copy, E: Ord + Clone + Default> Distances<Id, E>
+where for<'a> &'a E: Add<Output = E> {
+    /// Held–Karp: the cheapest way from `start` through each subset of `targets`,
+    /// ending at each target in it. There are `2^targets.len()` subsets, so keep
+    /// it to around 20 targets.
+    pub fn visiting_subsets(&self, start: &Id, targets: &[Id]) -> SubsetCosts<E> {
+        assert!(targets.len() < 32, "Too many targets to track as a bitmask");
+        let n = targets.len();
+        let mut costs = vec![None; n << n];
+        for (i, target) in targets.iter().enumerate() {
+            costs[(1 << i) * n + i] = self.get(start, target).cloned();
+        }
+        for mask in 1..1usize << n {
+            for last in (0..n).filter(|last| mask & 1 << last != 0) {
+                let Some(cost) = costs[mask * n + last].clone() else { continue };
+                for next in (0..n).filter(|next| mask & 1 << next == 0) {
+                    let Some(step) = self.get(&targets[last], &targets[next]) else { continue };
+                    let new_cost = &cost + step;
+                    let entry = &mut costs[(mask | 1 << next) * n + next];
+                    if entry.as_ref().is_none_or(|entry| &new_cost < entry) {
+                        *entry = Some(new_cost);
+                    }
+                }
+            }
+        }
+        SubsetCosts { targets: n, costs }
+    }
+
+    /// The cheapest way from `start` through all of `targets`, optionally
+    /// coming back to `start` at the end
+    pub fn cheapest_route(&self, start: &Id, targets: &[Id], round_trip: bool) -> Option<E> {
+        if targets.is_empty() { return Some(E::default()) }
+        let subsets = self.visiting_subsets(start, targets);
+        let all = (1 << targets.len()) - 1;
+        targets.iter()
+            .enumerate()
+            .flat_map(|(last, target)| {
+                let cost = subsets.cost(all, last)?;
+                if !round_trip { return Some(cost.clone()) }
+                Some(cost + self.get(target, start)?)
+            })
+            .min()
+    }
+}
+
+impl<E: Ord> SubsetCosts<E> {
+    /// Visiting the targets in `subset` and finishing at `targets[last]`
+    pub fn cost(&self, subset: usize, last: usize) -> Option<&E> {
+        if subset & 1 << last == 0 { return None }
+        self.costs[subset * self.targets + last].as_ref()
+    }
+    /// Visiting the targets in `subset` and finishing anywhere
+    pub fn cheapest(&self, subset: usize) -> Option<&E> {
+        (0..self.targets).flat_map(|last| self.cost(subset, last)).min()
+    }
+}
+
+#[test]
+fn test_routes() {
+    use super::{Graph, GraphAlgorithms};
+
+    let mut graph = Graph::<u8, (), usize>::new();
+    for &id in b"ABCDE" {
+        graph.insert_node(id, ());
+    }
+    graph.insert_edge(&b'A', &b'B', 7);
+    graph.insert_edge(&b'A', &b'E', 1);
+    graph.insert_edge(&b'B', &b'C', 3);
+    graph.insert_edge(&b'B', &b'E', 8);
+    graph.insert_edge(&b'C', &b'D', 6);
+    graph.insert_edge(&b'C', &b'E', 2);
+    graph.insert_edge(&b'D', &b'E', 7);
+
+    let distances = graph.floyd_warshall();
+    assert_eq!(distances.get(&b'A', &b'C'), Some(&3));
+    assert_eq!(distances.get(&b'B', &b'D'), Some(&9));
+    assert_eq!(distances.get(&b'D', &b'D'), Some(&0));
+
+    // A -> E -> C -> B, then back through C to D
+    let targets = *b"BCD";
+    assert_eq!(distances.cheapest_route(&b'A', &targets, false), Some(15));
+    assert_eq!(distances.cheapest_route(&b'A', &targets, true), Some(23));
+    let subsets = distances.visiting_subsets(&b'A', &targets);
+    assert_eq!(subsets.cost(0b011, 0), Some(&6));
+    assert_eq!(subsets.cheapest(0b011), Some(&6));
+    assert_eq!(subsets.cost(0b010, 0), None);
+
+    // A -> B -> E -> C -> D
+    assert_eq!(graph.longest_path(&b'A', &b'D'), Some(23));
+    graph.insert_node(b'Z', ());
+    assert_eq!(graph.longest_path(&b'A', &b'Z'), None);
+}
@@ -0,0 +1,121 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Write};
+use std::hash::Hash;
+
+use super::{Direction, Graph};
+
+/// Separators between a node and its neighbours in `from_edge_list`, most
+/// specific first
+const EDGE_SEPARATORS: &[&str] = &["<->", "->", ":", "-"];
+
+impl<Id: Hash + Eq + Copy + Debug, N, E, D: Direction> Graph<Id, N, E, D> {
+    /// The graph in Graphviz's DOT language, labelling nodes with their IDs
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with(|id, _| format!("{id:?}"), |_| None)
+    }
+}
+
+impl<Id: Hash + Eq + Copy, N, E, D: Direction> Graph<Id, N, E, D> {
+    /// The graph in Graphviz's DOT language, e.g. for `dot -Tsvg`. Edges only
+    /// get a label if `edge_label` gives one.
+    pub fn to_dot_with(
+        &self,
+        mut node_label: impl FnMut(&Id, &N) -> String,
+        mut edge_label: impl FnMut(&E) -> Option<String>,
+    ) -> String {
+        let (kind, edge_op) = if D::DIRECTED { ("digraph", "->") } else { ("graph", "--") };
+        let names: HashMap<Id, usize> = self.nodes.keys()
+            .enumerate()
+            .map(|(i, &id)| (id, i))
+            .collect();
+
+        let mut dot = format!("{kind} {{\n");
+        for (id, data) in self.nodes() {
+            writeln!(dot, "    n{} [label=\"{}\"];", names[id], escape(&node_label(id, data))).unwrap();
+        }
+        // Undirected edges are listed under both nodes
+        let mut written = HashSet::new();
+        for (id, (_, edges, _)) in &self.nodes {
+            for (other_id, edge_data_id) in edges {
+                if !written.insert(*edge_data_id) { continue }
+                let edge_data = self.edge_data
+                    .get(edge_data_id)
+                    .expect("Invariant violated: edge data ID missing associated data");
+                write!(dot, "    n{} {edge_op} n{}", names[id], names[other_id]).unwrap();
+                if let Some(label) = edge_label(edge_data) {
+                    write!(dot, " [label=\"{}\"]", escape(&label)).unwrap();
+                }
+                dot.push_str(";\n");
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+impl<'a, D: Direction> Graph<&'a str, (), (), D> {
+    /// Reads lines like `a -> b, c`, `a <-> b`, `a: b c` or `a-b`, with an edge
+    /// from the node on the left to each on the right, and back again for `<->`.
+    /// Blank lines are skipped.
+    pub fn from_edge_list(input: &'a str) -> Result<Self, String> {
+        let mut graph = Self::new();
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() { continue }
+            let Some((separator, (from, to))) = EDGE_SEPARATORS.iter()
+                .find_map(|&separator| Some((separator, line.split_once(separator)?)))
+            else {
+                return Err(format!("Line {} has no edge separator: {line}", i + 1));
+            };
+            let from = from.trim();
+            if from.is_empty() {
+                return Err(format!("Line {} has no node before the separator: {line}", i + 1));
+            }
+            graph.insert_or_update_node(from, ());
+            for to in to.split(|c: char| c == ',' || c.is_whitespace()).filter(|to| !to.is_empty()) {
+                graph.insert_or_update_node(to, ());
+                graph.insert_edge(&from, &to, ());
+                if D::DIRECTED && separator == "<->" {
+                    graph.insert_edge(&to, &from, ());
+                }
+            }
+        }
+        Ok(graph)
+    }
+}
+
+#[test]
+fn test_edge_list() {
+    use super::{DiGraph, GraphAlgorithms, Undirected};
+
+    let graph = DiGraph::from_edge_list("a -> b, c\nb -> c\n\nd:a c\n").unwrap();
+    let mut successors: Vec<_> = graph.successors(&"a").collect();
+    successors.sort();
+    assert_eq!(successors, vec!["b", "c"]);
+    assert_eq!(graph.predecessors(&"c").count(), 3);
+    assert_eq!(graph.topological_sort(), Some(vec!["d", "a", "b", "c"]));
+
+    let graph = DiGraph::from_edge_list("a <-> b, c\nc -> d").unwrap();
+    let mut successors: Vec<_> = graph.successors(&"c").collect();
+    successors.sort();
+    assert_eq!(successors, vec!["a", "d"]);
+    assert_eq!(graph.successors(&"b").collect::<Vec<_>>(), vec!["a"]);
+    assert_eq!(graph.predecessors(&"d").collect::<Vec<_>>(), vec!["c"]);
+    assert_eq!(graph.topological_sort(), None);
+
+    let graph = Graph::<_, _, _, Undirected>::from_edge_list("kh-tc\nqp-kh\n").unwrap();
+    assert_eq!(graph.successors(&"kh").count(), 2);
+    assert!(Graph::<_, _, _, Undirected>::from_edge_list("kh tc").is_err());
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("graph {\n"));
+    assert_eq!(dot.matches(" -- ").count(), 2);
+    assert!(dot.contains("[label=\"\\\"kh\\\"\"]"));
+    let dot = DiGraph::from_edge_list("a -> b").unwrap().to_dot_with(|id, _| id.to_string(), |_| Some("1".into()));
+    assert!(dot.starts_with("digraph {\n"));
+    assert!(dot.contains(" -> ") && dot.contains("[label=\"1\"]"));
+}
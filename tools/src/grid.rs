@@ -0,0 +1,257 @@
+use std::fmt::{Debug, Display};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// Positions a `Grid` can be indexed by. `pos!` implements it for its `Pos`.
+pub trait GridPos: Copy + 'static {
+    fn x(&self) -> i64;
+    fn y(&self) -> i64;
+    fn from_xy(x: i64, y: i64) -> Self;
+}
+
+/// Offsets to the orthogonal neighbours, then the diagonal ones, with y going down
+const NEIGHBOR_OFFSETS: [(i64, i64); 8] = [(0, -1), (1, 0), (0, 1), (-1, 0), (1, -1), (1, 1), (-1, 1), (-1, -1)];
+
+/// A rectangle of cells stored row by row, with (0, 0) at the top left
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "Expected {width}x{height} cells");
+        Self { width, height, cells }
+    }
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(|(x, y)| cell(x, y)).collect();
+        Self { width, height, cells }
+    }
+
+    /// One cell per character, skipping blank lines
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Self {
+        Self::try_parse(input, |c| Ok::<_, std::convert::Infallible>(cell(c))).unwrap_or_else(|e| panic!("{e}"))
+    }
+    /// One cell per character, skipping blank lines. Every line must be the same length.
+    pub fn try_parse<E: Debug>(input: &str, mut cell: impl FnMut(char) -> Result<T, E>) -> Result<Self, String> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let len_before = cells.len();
+            for c in line.chars() {
+                cells.push(cell(c).map_err(|e| format!("Invalid cell {c:?} on line {}: {e:?}", height + 1))?);
+            }
+            let line_width = cells.len() - len_before;
+            if *width.get_or_insert(line_width) != line_width {
+                return Err(format!("Line {} is {line_width} cells wide, not {}", height + 1, width.unwrap()));
+            }
+            height += 1;
+        }
+        Ok(Self { width: width.unwrap_or(0), height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of<P: GridPos>(&self, pos: P) -> Option<usize> {
+        let (x, y) = (pos.x(), pos.y());
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+        Some(y as usize * self.width + x as usize)
+    }
+    pub fn in_bounds<P: GridPos>(&self, pos: P) -> bool {
+        self.index_of(pos).is_some()
+    }
+    pub fn get<P: GridPos>(&self, pos: P) -> Option<&T> {
+        self.cells.get(self.index_of(pos)?)
+    }
+    pub fn get_mut<P: GridPos>(&mut self, pos: P) -> Option<&mut T> {
+        let i = self.index_of(pos)?;
+        self.cells.get_mut(i)
+    }
+    /// As if the grid repeated forever in every direction
+    pub fn get_wrapping<P: GridPos>(&self, pos: P) -> &T {
+        let x = pos.x().rem_euclid(self.width as i64) as usize;
+        let y = pos.y().rem_euclid(self.height as i64) as usize;
+        &self.cells[y * self.width + x]
+    }
+
+    /// The orthogonal neighbours inside the grid
+    pub fn neighbors4<P: GridPos>(&self, pos: P) -> impl Iterator<Item = P> + '_ {
+        self.neighbors_from(pos, &NEIGHBOR_OFFSETS[..4])
+    }
+    /// The orthogonal and diagonal neighbours inside the grid
+    pub fn neighbors8<P: GridPos>(&self, pos: P) -> impl Iterator<Item = P> + '_ {
+        self.neighbors_from(pos, &NEIGHBOR_OFFSETS)
+    }
+    fn neighbors_from<'a, P: GridPos>(&'a self, pos: P, offsets: &'static [(i64, i64)]) -> impl Iterator<Item = P> + 'a {
+        offsets.iter()
+            .map(move |&(dx, dy)| P::from_xy(pos.x() + dx, pos.y() + dy))
+            .filter(|&neighbor| self.in_bounds(neighbor))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(x < self.width, "Column {x} is outside the {}x{} grid", self.width, self.height);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter()
+    }
+    pub fn positions<P: GridPos>(&self) -> impl Iterator<Item = (P, &T)> + '_ {
+        self.cells.iter()
+            .enumerate()
+            .map(|(i, cell)| (P::from_xy((i % self.width) as i64, (i / self.width) as i64), cell))
+    }
+    pub fn find<P: GridPos>(&self, mut pred: impl FnMut(&T) -> bool) -> Option<P> {
+        self.positions().find(|(_, cell)| pred(cell)).map(|(pos, _)| pos)
+    }
+    pub fn count(&self, mut pred: impl FnMut(&T) -> bool) -> usize {
+        self.cells.iter().filter(|cell| pred(cell)).count()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self { width, height, cells: vec![fill; width * height] }
+    }
+
+    /// Swaps x and y
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| self.cells[x * self.width + y].clone())
+    }
+    /// A quarter turn clockwise, with y going down
+    pub fn rotate_right(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| self.cells[(self.height - 1 - x) * self.width + y].clone())
+    }
+    /// A quarter turn anticlockwise, with y going down
+    pub fn rotate_left(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| self.cells[x * self.width + self.width - 1 - y].clone())
+    }
+    /// Mirrors left to right
+    pub fn flip_horizontal(&self) -> Self {
+        Self::from_fn(self.width, self.height, |x, y| self.cells[y * self.width + self.width - 1 - x].clone())
+    }
+    /// Mirrors top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        Self::from_fn(self.width, self.height, |x, y| self.cells[(self.height - 1 - y) * self.width + x].clone())
+    }
+    /// The `width`x`height` cells with `(x, y)` at the top left
+    pub fn sub_grid(&self, x: usize, y: usize, width: usize, height: usize) -> Self {
+        assert!(
+            x + width <= self.width && y + height <= self.height,
+            "A {width}x{height} grid at ({x}, {y}) doesn't fit in the {}x{} grid", self.width, self.height,
+        );
+        Self::from_fn(width, height, |dx, dy| self.cells[(y + dy) * self.width + x + dx].clone())
+    }
+}
+
+impl<T, P: GridPos> Index<P> for Grid<T> {
+    type Output = T;
+    fn index(&self, pos: P) -> &T {
+        let Some(i) = self.index_of(pos) else {
+            panic!("({}, {}) is outside the {}x{} grid", pos.x(), pos.y(), self.width, self.height);
+        };
+        &self.cells[i]
+    }
+}
+impl<T, P: GridPos> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, pos: P) -> &mut T {
+        let Some(i) = self.index_of(pos) else {
+            panic!("({}, {}) is outside the {}x{} grid", pos.x(), pos.y(), self.width, self.height);
+        };
+        &mut self.cells[i]
+    }
+}
+
+/// Cells are parsed with their `TryFrom<char>`, e.g. for an enum of tiles
+impl<T: TryFrom<char>> FromStr for Grid<T> where T::Error: Debug {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse(s, T::try_from)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Debug> Debug for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                if f.alternate() {
+                    write!(f, "{cell:#?}")?;
+                } else {
+                    write!(f, "{cell:?}")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_grid() {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Pos { x: i8, y: i8 }
+    impl GridPos for Pos {
+        fn x(&self) -> i64 { self.x as i64 }
+        fn y(&self) -> i64 { self.y as i64 }
+        fn from_xy(x: i64, y: i64) -> Self { Self { x: x as i8, y: y as i8 } }
+    }
+
+    let grid: Grid<char> = "abc\ndef\n".parse().unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[Pos { x: 2, y: 1 }], 'f');
+    assert_eq!(grid.get(Pos { x: 3, y: 0 }), None);
+    assert_eq!(*grid.get_wrapping(Pos { x: -1, y: 2 }), 'c');
+    assert_eq!(grid.neighbors4(Pos { x: 0, y: 0 }).collect::<Vec<_>>(), vec![Pos { x: 1, y: 0 }, Pos { x: 0, y: 1 }]);
+    assert_eq!(grid.neighbors8(Pos { x: 1, y: 0 }).count(), 5);
+    assert_eq!(grid.find(|&c| c == 'e'), Some(Pos { x: 1, y: 1 }));
+    assert_eq!(grid.column(1).collect::<String>(), "be");
+
+    assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+    assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+    assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+    assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+    assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+    assert_eq!(grid.sub_grid(1, 0, 2, 2).to_string(), "bc\nef");
+    assert_eq!(grid.rotate_right().rotate_right().rotate_right().rotate_right(), grid);
+
+    let mut digits = Grid::parse("12\n34", |c| c as u8 - b'0');
+    digits[Pos { x: 0, y: 0 }] = 9;
+    assert_eq!(format!("{digits:?}"), "92\n34\n");
+    assert!("ab\nc".parse::<Grid<char>>().is_err());
+}
@@ -0,0 +1,164 @@
+use crate::sparse_grid::SparsePos;
+
+/// A hexagon in axial coordinates: `q` counts columns and `r` counts rows,
+/// going down. The cube coordinates add `s = -q - r`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+/// Which way up the hexagons are, which decides the directions between them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    /// A point at the top, in rows: `e`, `w`, `ne`, `nw`, `se` and `sw`
+    PointyTop,
+    /// A flat side at the top, in columns: `n`, `s`, `ne`, `nw`, `se` and `sw`
+    FlatTop,
+}
+
+impl Hex {
+    pub const ORIGIN: Self = Self { q: 0, r: 0 };
+    /// The neighbouring offsets, clockwise
+    pub const DIRECTIONS: [Self; 6] = [
+        Self { q: 1, r: 0 },
+        Self { q: 0, r: 1 },
+        Self { q: -1, r: 1 },
+        Self { q: -1, r: 0 },
+        Self { q: 0, r: -1 },
+        Self { q: 1, r: -1 },
+    ];
+
+    pub const fn new(q: i32, r: i32) -> Self {
+        Self { q, r }
+    }
+    pub fn from_cube(q: i32, r: i32, s: i32) -> Self {
+        assert_eq!(q + r + s, 0, "Cube coordinates ({q}, {r}, {s}) don't add up to 0");
+        Self { q, r }
+    }
+    pub const fn s(&self) -> i32 {
+        -self.q - self.r
+    }
+    pub const fn cube(&self) -> (i32, i32, i32) {
+        (self.q, self.r, self.s())
+    }
+
+    /// The offset for a direction like `ne`, in either case
+    pub fn direction(name: &str, orientation: Orientation) -> Option<Self> {
+        let (q, r) = match (orientation, name.to_ascii_lowercase().as_str()) {
+            (Orientation::PointyTop, "e") => (1, 0),
+            (Orientation::PointyTop, "w") => (-1, 0),
+            (Orientation::PointyTop, "ne") => (1, -1),
+            (Orientation::PointyTop, "nw") => (0, -1),
+            (Orientation::PointyTop, "se") => (0, 1),
+            (Orientation::PointyTop, "sw") => (-1, 1),
+            (Orientation::FlatTop, "n") => (0, -1),
+            (Orientation::FlatTop, "s") => (0, 1),
+            (Orientation::FlatTop, "ne") => (1, -1),
+            (Orientation::FlatTop, "nw") => (-1, 0),
+            (Orientation::FlatTop, "se") => (1, 0),
+            (Orientation::FlatTop, "sw") => (-1, 1),
+            _ => return None,
+        };
+        Some(Self { q, r })
+    }
+    /// Directions either run together, like `esenee`, or separated by commas
+    /// or whitespace, like `ne,ne,s`
+    pub fn parse_path(input: &str, orientation: Orientation) -> Result<Vec<Self>, String> {
+        let mut path = vec![];
+        let mut rest = input.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        while !rest.is_empty() {
+            let (step, len) = rest.get(..2)
+                .and_then(|name| Some((Self::direction(name, orientation)?, 2)))
+                .or_else(|| Some((Self::direction(rest.get(..1)?, orientation)?, 1)))
+                .ok_or_else(|| format!("Invalid {orientation:?} hex direction at {rest:?}"))?;
+            path.push(step);
+            rest = rest[len..].trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        }
+        Ok(path)
+    }
+
+    pub const fn add(&self, o: Self) -> Self {
+        Self { q: self.q + o.q, r: self.r + o.r }
+    }
+    pub const fn sub(&self, o: Self) -> Self {
+        Self { q: self.q - o.q, r: self.r - o.r }
+    }
+    pub const fn neg(&self) -> Self {
+        Self { q: -self.q, r: -self.r }
+    }
+    pub const fn mul(&self, s: i32) -> Self {
+        Self { q: self.q * s, r: self.r * s }
+    }
+
+    /// The number of steps between the two
+    pub const fn distance(&self, o: Self) -> i32 {
+        let d = self.sub(o);
+        (d.q.abs() + d.r.abs() + d.s().abs()) / 2
+    }
+    /// A sixth of a turn clockwise around the origin
+    pub const fn rotate_right(&self) -> Self {
+        Self { q: -self.r, r: -self.s() }
+    }
+    /// A sixth of a turn anticlockwise around the origin
+    pub const fn rotate_left(&self) -> Self {
+        Self { q: -self.s(), r: -self.q }
+    }
+
+    /// The hexes exactly `radius` steps away, clockwise from the top left corner
+    pub fn ring(self, radius: u32) -> impl Iterator<Item = Self> {
+        let radius = radius as i32;
+        let corner = self.add(Self::DIRECTIONS[4].mul(radius));
+        let sides = Self::DIRECTIONS.into_iter().flat_map(move |dir| std::iter::repeat_n(dir, radius as usize));
+        let steps = sides.scan(corner, |hex, dir| {
+            let curr = *hex;
+            *hex = hex.add(dir);
+            Some(curr)
+        });
+        // A ring of radius 0 is just the centre
+        std::iter::once(self).filter(move |_| radius == 0).chain(steps)
+    }
+    /// The hexes up to `radius` steps away, ring by ring from this one
+    pub fn spiral(self, radius: u32) -> impl Iterator<Item = Self> {
+        (0..=radius).flat_map(move |ring| self.ring(ring))
+    }
+}
+
+/// Hexes can fill a `SparseGrid`, with their 6 neighbours
+impl SparsePos for Hex {
+    const DIMENSIONS: usize = 2;
+    fn coord(&self, axis: usize) -> i64 {
+        [self.q, self.r][axis] as i64
+    }
+    fn from_coords(mut coord: impl FnMut(usize) -> i64) -> Self {
+        Self { q: coord(0) as i32, r: coord(1) as i32 }
+    }
+    fn neighbors(self) -> impl Iterator<Item = Self> {
+        Self::DIRECTIONS.into_iter().map(move |dir| self.add(dir))
+    }
+}
+
+#[test]
+fn test_hex() {
+    let path = Hex::parse_path("nwwswee", Orientation::PointyTop).unwrap();
+    assert_eq!(path.into_iter().fold(Hex::ORIGIN, |a, b| a.add(b)), Hex::ORIGIN);
+    let path = Hex::parse_path("ne,ne,s,s", Orientation::FlatTop).unwrap();
+    assert_eq!(path.into_iter().fold(Hex::ORIGIN, |a, b| a.add(b)).distance(Hex::ORIGIN), 2);
+    let path = Hex::parse_path("se,sw,se,sw,sw", Orientation::FlatTop).unwrap();
+    assert_eq!(path.into_iter().fold(Hex::ORIGIN, |a, b| a.add(b)).distance(Hex::ORIGIN), 3);
+    assert!(Hex::parse_path("nen", Orientation::PointyTop).is_err());
+
+    let hex = Hex::from_cube(2, -3, 1);
+    assert_eq!(hex.cube(), (2, -3, 1));
+    assert_eq!(hex.rotate_right().rotate_right().rotate_right(), hex.neg());
+    assert_eq!(hex.rotate_left().rotate_right(), hex);
+    assert_eq!(Hex::DIRECTIONS[0].rotate_right(), Hex::DIRECTIONS[1]);
+
+    let ring: Vec<Hex> = hex.ring(2).collect();
+    assert_eq!(ring.len(), 12);
+    assert!(ring.iter().all(|other| other.distance(hex) == 2));
+    assert!(ring.windows(2).all(|pair| pair[0].distance(pair[1]) == 1));
+    assert_eq!(hex.ring(0).collect::<Vec<_>>(), vec![hex]);
+    assert_eq!(hex.spiral(3).count(), 37);
+    assert_eq!(hex.neighbors().count(), 6);
+}
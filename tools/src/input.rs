@@ -0,0 +1,91 @@
+use std::io::Read;
+use std::path::PathBuf;
+
+/// Where a day's `main` should read its puzzle input from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `data/<year>/<day>/input.txt`
+    Data,
+    /// `data/<year>/<day>/test.txt`
+    Test,
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Accepts `--input <path>` (`-` for stdin), `--stdin` and `--test`,
+    /// falling back to `default` when none are given
+    pub fn from_args(mut args: impl Iterator<Item = String>, default: Self) -> Result<Self, String> {
+        let mut source = None;
+        while let Some(arg) = args.next() {
+            let new_source = match arg.as_str() {
+                "--test" => Self::Test,
+                "--stdin" => Self::Stdin,
+                "--input" => match args.next().as_deref() {
+                    Some("-") => Self::Stdin,
+                    Some(path) => Self::Path(PathBuf::from(path)),
+                    None => return Err("Missing path after --input".to_string()),
+                },
+                _ => match arg.strip_prefix("--input=") {
+                    Some("-") => Self::Stdin,
+                    Some(path) => Self::Path(PathBuf::from(path)),
+                    None => continue,
+                },
+            };
+            if source.replace(new_source).is_some() {
+                return Err("Only one of --input, --stdin or --test may be given".to_string());
+            }
+        }
+        Ok(source.unwrap_or(default))
+    }
+
+    /// Reads the input, using `default_dir` and `day_path` (`<year>/<day>`) to
+    /// locate the data directory files
+    pub fn read(&self, default_dir: &str, day_path: &str) -> std::io::Result<String> {
+        match self {
+            Self::Data => std::fs::read_to_string(data_path(default_dir, day_path, "input.txt")),
+            Self::Test => std::fs::read_to_string(data_path(default_dir, day_path, "test.txt")),
+            Self::Path(path) => std::fs::read_to_string(path),
+            Self::Stdin => {
+                let mut input = String::new();
+                std::io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            },
+        }
+    }
+}
+
+/// The `data` directory, overridable with the `AOC_DATA_DIR` environment variable
+pub fn data_dir(default: &str) -> PathBuf {
+    match std::env::var_os("AOC_DATA_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(default),
+    }
+}
+
+pub fn data_path(default_dir: &str, day_path: &str, file: &str) -> PathBuf {
+    data_dir(default_dir).join(day_path).join(file)
+}
+
+pub fn read_data_file(default_dir: &str, day_path: &str, file: &str) -> String {
+    let path = data_path(default_dir, day_path, file);
+    match std::fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(e) => panic!("Could not read input file {}: {e}", path.display()),
+    }
+}
+
+#[test]
+fn test_input_source_args() {
+    let args = |args: &[&str]| args.iter().map(|s| s.to_string()).collect::<Vec<_>>().into_iter();
+
+    assert_eq!(InputSource::from_args(args(&[]), InputSource::Data), Ok(InputSource::Data));
+    assert_eq!(InputSource::from_args(args(&["--test"]), InputSource::Data), Ok(InputSource::Test));
+    assert_eq!(InputSource::from_args(args(&["--input", "-"]), InputSource::Data), Ok(InputSource::Stdin));
+    assert_eq!(
+        InputSource::from_args(args(&["--input=a.txt"]), InputSource::Test),
+        Ok(InputSource::Path(PathBuf::from("a.txt"))),
+    );
+    assert!(InputSource::from_args(args(&["--input"]), InputSource::Data).is_err());
+    assert!(InputSource::from_args(args(&["--test", "--stdin"]), InputSource::Data).is_err());
+}
@@ -1,65 +1,176 @@
+pub mod answers;
+pub mod examples;
 pub mod graph;
+pub mod grid;
+pub mod hex;
+pub mod input;
+pub mod ocr;
+pub mod params;
+pub mod search;
+pub mod sparse_grid;
+pub mod timing;
 
 #[macro_export]
 macro_rules! aoc_sol {
-    ($day:ident $($year:literal)? $($input_type:ident)?: $($part_fn:ident),+) => {
+    ($day:ident $($year:literal)? $($input_type:ident)?: $($part_fn:ident),+ $(; params: $params:ty)? $(; examples: $($example:ident),+)? $(,)?) => {
         #[allow(dead_code)]
         use std::fmt::Debug;
         #[allow(dead_code)]
         use std::collections::{ HashSet, HashMap };
 
+        /// The day's `Params`, or `()` if the parts only take the input
+        pub type AocParams = $crate::aoc_sol!(@impl params $($params)?);
+
+        /// Builds the `AocParams` that `AOC_PARTS` take
+        pub const AOC_PARAMS: $crate::params::ParamsFn = $crate::params::DayParams::from_preset::<AocParams>;
+
+        /// Every part of the day in order, for the workspace runner
+        pub const AOC_PARTS: &[$crate::params::PartFn] = &[
+            $(|input, params| $crate::params::call_with_params::<AocParams, _, _>(&$part_fn, input, params)),+
+        ];
+
         #[allow(dead_code)]
         pub fn main() {
-            #[allow(dead_code)]
-            const TEST: &str = $crate::input_file!(relative $day $(($year))? -> "test.txt");
-            const INPUT: &str = $crate::input_file!(relative $day $(($year))? -> "input.txt");
-
-            let input = $crate::aoc_sol!(@impl input_type $($input_type)?, TEST, INPUT);
+            let default_source = $crate::aoc_sol!(@impl input_type $($input_type)?);
+            let source = match $crate::input::InputSource::from_args(std::env::args().skip(1), default_source) {
+                Ok(source) => source,
+                Err(e) => {
+                    eprintln!("{e}");
+                    eprintln!("Usage: [--input <path> | --input - | --stdin | --test]");
+                    std::process::exit(2);
+                },
+            };
+            let verify = std::env::var("VERIFY_OUTPUT").is_ok();
+            let record = std::env::var("RECORD_OUTPUT").is_ok();
+            if (verify || record) && source != $crate::input::InputSource::Data {
+                eprintln!("VERIFY_OUTPUT and RECORD_OUTPUT only apply to the real input, not {source:?}");
+                std::process::exit(2);
+            }
+            let input = match $crate::input_file!(relative $day $(($year))? <- source) {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("Could not read input ({source:?}): {e}");
+                    std::process::exit(1);
+                },
+            };
+            let input = input.as_str();
+            let params = <AocParams as $crate::params::Params>::from_preset(&$crate::params::Preset::for_source(&source))
+                .unwrap_or_else(|e| panic!("{e}"));
+            let iterations = std::env::var("TIMING_ITERATIONS").ok()
+                .and_then(|iterations| iterations.parse().ok())
+                .unwrap_or(1);
             let mut parts = Vec::new();
+            let mut timings = Vec::new();
 
             let mut i = 1;
             $({
-                let part_start = std::time::Instant::now();
-                let part = $part_fn(input);
-                let part_time = part_start.elapsed();
+                let (part, timing) = $crate::timing::measure(iterations, || {
+                    $crate::answers::Answer::from($crate::params::Part::call(&$part_fn, input, &params))
+                });
+                let part_time = timing.median;
                 println!("Part {i}: {part} ({part_time:?})");
                 parts.push(part.to_string());
+                timings.push(timing);
                 i += 1;
             })+
 
-            if std::env::var("VERIFY_OUTPUT").is_ok() {
-                let year = [$($year,)? 2024][0].to_string();
-                $crate::verify(stringify!($day), &year, parts.into_iter());
+            let year: u16 = [$($year,)? 2024][0];
+            let day_path = format!("{year}/{}", stringify!($day));
+            if let Ok(path) = std::env::var("TIMINGS_OUTPUT") {
+                let day = stringify!($day).trim_start_matches("day").parse().unwrap();
+                let commit = $crate::timing::git_commit();
+                let records: Vec<_> = timings.iter()
+                    .zip(&parts)
+                    .enumerate()
+                    .map(|(i, (timing, answer))| {
+                        $crate::timing::TimingRecord::new(year, day, i as u8 + 1, timing, answer, commit.clone())
+                    })
+                    .collect();
+                if let Err(e) = $crate::timing::append_records(std::path::Path::new(&path), &records) {
+                    eprintln!("Could not write timings to {path}: {e}");
+                }
+            }
+            if verify {
+                $crate::verify($crate::aoc_sol!(@impl data_dir), &day_path, parts.iter().cloned());
+            }
+            if record {
+                $crate::answers::record_answers($crate::aoc_sol!(@impl data_dir), &day_path, parts.into_iter());
             }
         }
 
         #[test]
         fn verify_outputs() {
-            #[allow(dead_code)]
-            const TEST: &str = $crate::input_file!(relative $day $(($year))? -> "test.txt");
-            const INPUT: &str = $crate::input_file!(relative $day $(($year))? -> "input.txt");
-
-            let input = $crate::aoc_sol!(@impl input_type $($input_type)?, TEST, INPUT);
+            let source = $crate::aoc_sol!(@impl input_type $($input_type)?);
+            let input = $crate::input_file!(relative $day $(($year))? <- source)
+                .expect("Could not read input");
+            let input = input.as_str();
+            let params = <AocParams as $crate::params::Params>::from_preset(&$crate::params::Preset::for_source(&source))
+                .expect("Invalid params");
             let mut parts = Vec::new();
 
             let mut i = 1;
             $({
                 let part_start = std::time::Instant::now();
-                let part = $part_fn(input);
+                let part = $crate::answers::Answer::from($crate::params::Part::call(&$part_fn, input, &params));
                 let part_time = part_start.elapsed();
                 parts.push(part.to_string());
                 i += 1;
             })+
 
-            let year = [$($year,)? 2024][0].to_string();
-            $crate::verify(stringify!($day), &year, parts.into_iter());
+            assert!(
+                $crate::verify($crate::aoc_sol!(@impl data_dir), &aoc_day_path(), parts.into_iter()),
+                "Outputs did not match the recorded answers",
+            );
+        }
+
+        /// `<year>/<day>`, locating the day's files in the data directory
+        #[allow(dead_code)]
+        fn aoc_day_path() -> String {
+            format!("{}/{}", [$($year,)? 2024][0], stringify!($day))
+        }
+
+        #[test]
+        fn examples_listed() {
+            $crate::examples::test_examples_listed(
+                $crate::aoc_sol!(@impl data_dir),
+                &aoc_day_path(),
+                &[$($(stringify!($example)),+)?],
+            );
         }
+
+        $(
+            // One test per example in the day's `examples.toml`
+            #[cfg(test)]
+            mod aoc_examples {
+                $(
+                    #[test]
+                    fn $example() {
+                        $crate::examples::test_example(
+                            $crate::aoc_sol!(@impl data_dir),
+                            &super::aoc_day_path(),
+                            stringify!($example),
+                            super::AOC_PARAMS,
+                            super::AOC_PARTS,
+                        );
+                    }
+                )+
+            }
+        )?
+    };
+    (@impl params $params:ty) => {
+        $params
+    };
+    (@impl params) => {
+        ()
+    };
+    (@impl data_dir) => {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/../../data")
     };
-    (@impl input_type test, $test:ident, $input:ident) => {
-        $test
+    (@impl input_type test) => {
+        $crate::input::InputSource::Test
     };
-    (@impl input_type , $test:ident, $input:ident) => {
-        $input
+    (@impl input_type) => {
+        $crate::input::InputSource::Data
     };
 }
 
@@ -72,25 +183,31 @@ macro_rules! day_bench {
             use criterion::{ criterion_group, Criterion, SamplingMode::AutoMin };
             use std::hint::black_box;
 
-            const INPUT: &str = $crate::input_file!(bench $day $(($year))? -> "input.txt");
-
             fn p1(c: &mut criterion::Criterion) {
+                let input = $crate::input_file!(bench $day $(($year))? -> "input.txt");
+                let params = <::$module::AocParams as Default>::default();
                 let number = stringify!($day).trim_start_matches("day");
                 #[allow(dead_code)]
                 let name = format!("Day {number} Part 1");
                 $(
                     let name = format!("{} day {number} Part 1", $year);
                 )?
-                c.bench_function(&name, |b| b.iter(|| ::$module::part1(black_box(INPUT))));
+                c.bench_function(&name, |b| b.iter(|| {
+                    $crate::params::Part::call(&::$module::part1, black_box(&*input), &params)
+                }));
             }
 
             fn p2(c: &mut criterion::Criterion) {
+                let input = $crate::input_file!(bench $day $(($year))? -> "input.txt");
+                let params = <::$module::AocParams as Default>::default();
                 let number = stringify!($day).trim_start_matches("day");
                 let name = format!("Day {number} Part 2");
                 $(
                     let name = format!("{} day {number} Part 2", $year);
                 )?
-                c.bench_function(&name, |b| b.iter(|| ::$module::part2(black_box(INPUT))));
+                c.bench_function(&name, |b| b.iter(|| {
+                    $crate::params::Part::call(&::$module::part2, black_box(&*input), &params)
+                }));
             }
 
             criterion_group! {
@@ -104,7 +221,10 @@ macro_rules! day_bench {
     };
 }
 
+/// With the `embed-input` feature, data files are baked in with `include_str!`
+/// and the `->` forms evaluate to a `&'static str`
 #[macro_export]
+#[cfg(feature = "embed-input")]
 macro_rules! input_file {
     (bench $day:ident ($year:literal) -> $file:literal) => {
         include_str!(concat!("../../data/", $year, "/", stringify!($day), "/", $file))
@@ -118,6 +238,58 @@ macro_rules! input_file {
     (relative $day:ident -> $file:literal) => {
         include_str!(concat!("../../data/", stringify!($day), "/", $file))
     };
+    (relative $day:ident $(($year:literal))? <- $source:expr) => {
+        match &$source {
+            $crate::input::InputSource::Data => Ok::<String, std::io::Error>(
+                $crate::input_file!(relative $day $(($year))? -> "input.txt").to_string()
+            ),
+            $crate::input::InputSource::Test => Ok(
+                $crate::input_file!(relative $day $(($year))? -> "test.txt").to_string()
+            ),
+            // Only external sources are left, so the data directory is never used
+            source => source.read("", ""),
+        }
+    };
+}
+
+/// Without the `embed-input` feature, data files are read at runtime from the
+/// `data` directory (or `AOC_DATA_DIR`) and the `->` forms evaluate to a
+/// `String`, panicking if the file is missing
+///
+/// The `<-` forms read from an `input::InputSource` and evaluate to an
+/// `io::Result<String>`
+#[macro_export]
+#[cfg(not(feature = "embed-input"))]
+macro_rules! input_file {
+    (bench $day:ident ($year:literal) -> $file:literal) => {
+        $crate::input::read_data_file(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../data"),
+            concat!($year, "/", stringify!($day)),
+            $file,
+        )
+    };
+    (relative $day:ident ($year:literal) -> $file:literal) => {
+        $crate::input::read_data_file(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../data"),
+            concat!($year, "/", stringify!($day)),
+            $file,
+        )
+    };
+    (bench $day:ident -> $file:literal) => {
+        $crate::input::read_data_file(concat!(env!("CARGO_MANIFEST_DIR"), "/../data"), stringify!($day), $file)
+    };
+    (relative $day:ident -> $file:literal) => {
+        $crate::input::read_data_file(concat!(env!("CARGO_MANIFEST_DIR"), "/../data"), stringify!($day), $file)
+    };
+    (relative $day:ident ($year:literal) <- $source:expr) => {
+        $source.read(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../data"),
+            concat!($year, "/", stringify!($day)),
+        )
+    };
+    (relative $day:ident <- $source:expr) => {
+        $source.read(concat!(env!("CARGO_MANIFEST_DIR"), "/../data"), stringify!($day))
+    };
 }
 
 #[macro_export]
@@ -131,17 +303,15 @@ macro_rules! multi_day_bench {
             use std::hint::black_box;
 
             const INPUT_COUNT: u64 = [$(stringify!($day),)+].len() as u64;
-            #[allow(non_upper_case_globals)]
-            mod inputs {
-                $crate::multi_day_bench! { @impl $($year)?; $($day),+ }
-            }
 
             fn multiday_fn(c: &mut criterion::Criterion) {
+                $crate::multi_day_bench! { @impl $($year)?; $($day),+ }
                 let name = concat!("Multiday ", stringify!($multiday_name));
                 c.bench_function(name, |b| b.iter(|| {
                     $(
-                        ::$module::part1(black_box(inputs::$day));
-                        ::$module::part2(black_box(inputs::$day));
+                        let params = <::$module::AocParams as Default>::default();
+                        $crate::params::Part::call(&::$module::part1, black_box(&*$day), &params);
+                        $crate::params::Part::call(&::$module::part2, black_box(&*$day), &params);
                     )+
                 }));
             }
@@ -155,7 +325,7 @@ macro_rules! multi_day_bench {
     };
     (@impl $($year:literal)?;) => {};
     (@impl $($year:literal)?; $day:ident$(,)? $($rest:ident),*) => {
-        pub const $day: &str = include_str!(concat!("../../data/", $($year, "/",)? stringify!($day), "/input.txt"));
+        let $day = $crate::input_file!(bench $day $(($year))? -> "input.txt");
         $crate::multi_day_bench! { @impl $($year)?; $($rest),* }
     };
 }
@@ -228,6 +398,16 @@ macro_rules! pos3 {
                 x_diff + y_diff + z_diff
             }
         }
+
+        impl $crate::sparse_grid::SparsePos for Pos3 {
+            const DIMENSIONS: usize = 3;
+            fn coord(&self, axis: usize) -> i64 {
+                [self.x, self.y, self.z][axis] as i64
+            }
+            fn from_coords(mut coord: impl FnMut(usize) -> i64) -> Self {
+                Self { x: coord(0) as $inner_type, y: coord(1) as $inner_type, z: coord(2) as $inner_type }
+            }
+        }
     };
 }
 
@@ -352,6 +532,28 @@ macro_rules! pos {
                 }
             }
         } derives: $($($derives)+)?);
+
+        impl $crate::grid::GridPos for Pos {
+            fn x(&self) -> i64 {
+                self.x as i64
+            }
+            fn y(&self) -> i64 {
+                self.y as i64
+            }
+            fn from_xy(x: i64, y: i64) -> Self {
+                Self { x: x as $inner_type, y: y as $inner_type }
+            }
+        }
+
+        impl $crate::sparse_grid::SparsePos for Pos {
+            const DIMENSIONS: usize = 2;
+            fn coord(&self, axis: usize) -> i64 {
+                [self.x, self.y][axis] as i64
+            }
+            fn from_coords(mut coord: impl FnMut(usize) -> i64) -> Self {
+                Self { x: coord(0) as $inner_type, y: coord(1) as $inner_type }
+            }
+        }
     };
 
     (@impl +y=>$(U)?$(UP)?$(up)?; N ($inner_type:ty)) => { 1 };
@@ -495,31 +697,24 @@ use std::ops::{Deref, DerefMut};
 #[doc(hidden)]
 pub use ferroc as __hidden_ferroc;
 
-pub fn get_cookie() -> String {
-    std::fs::read_to_string(".aoc_cookie").unwrap()
-}
-
-pub fn get_page(day: &str, year: &str) -> String {
-    reqwest::blocking::Client::new()
-        .get(format!("https://adventofcode.com/{year}/day/{day}"))
-        .header("Cookie", get_cookie())
-        .send()
-        .unwrap()
-        .text()
-        .unwrap()
-}
-
-pub fn verify(day: &str, year: &str, values: impl Iterator<Item = String>) {
-    let day = day.trim_start_matches("day").trim_start_matches('0');
-    let page = get_page(day, year);
+/// Checks each part's output against `data/<year>/<day>/answers.toml`,
+/// returning `false` if any of them are known to be wrong
+pub fn verify(default_dir: &str, day_path: &str, values: impl Iterator<Item = String>) -> bool {
+    let answers = answers::Answers::load(&answers::Answers::path(default_dir, day_path))
+        .unwrap_or_else(|e| panic!("{e}"));
 
+    let mut all_correct = true;
     for (i, output) in values.enumerate() {
-        if page.contains(&output) {
-            println!("Part {} verified", i + 1);
-        } else {
-            println!("Part {} ERROR", i + 1);
+        match answers.verify(i + 1, &output) {
+            answers::Verification::Correct => println!("Part {} verified", i + 1),
+            answers::Verification::Incorrect { expected, actual } => {
+                println!("Part {} ERROR: expected {expected}, got {actual}", i + 1);
+                all_correct = false;
+            },
+            answers::Verification::Unknown => println!("Part {} has no recorded answer", i + 1),
         }
     }
+    all_correct
 }
 
 #[macro_export]
@@ -788,6 +983,7 @@ impl<const N: usize, T> std::ops::IndexMut<usize> for SmallVec<N, T> {
     }
 }
 
+/// New days should use `grid::Grid::parse`
 pub fn parse_map<Cell>(input: &str, parser: impl Fn(char) -> Cell) -> Vec<Vec<Cell>> {
     input.lines()
         .filter(|l| !l.trim().is_empty())
@@ -795,6 +991,7 @@ pub fn parse_map<Cell>(input: &str, parser: impl Fn(char) -> Cell) -> Vec<Vec<Ce
         .collect()
 }
 
+/// New days should use `grid::Grid`
 #[macro_export]
 macro_rules! map_struct {
     ($name:ident of $type:tt $({ $($additional_fields:tt)+ })?, pos $($pos_info:tt)+) => {
@@ -0,0 +1,183 @@
+use std::fmt::Display;
+
+/// A monochrome image, e.g. the letters painted by a puzzle's output
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PixelGrid {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+
+impl PixelGrid {
+    pub fn new(width: usize, height: usize, pixels: Vec<bool>) -> Self {
+        assert_eq!(pixels.len(), width * height, "Expected {width}x{height} pixels");
+        Self { width, height, pixels }
+    }
+
+    /// Rows of lit (`true`) and unlit pixels. Short rows are padded with unlit pixels.
+    pub fn from_rows<R: IntoIterator<Item = bool>>(rows: impl IntoIterator<Item = R>) -> Self {
+        let rows: Vec<Vec<bool>> = rows.into_iter().map(|row| row.into_iter().collect()).collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let pixels = rows.iter()
+            .flat_map(|row| row.iter().copied().chain(std::iter::repeat(false)).take(width))
+            .collect();
+        Self::new(width, rows.len(), pixels)
+    }
+
+    /// The lit pixels' coordinates, which can be anywhere (including negative).
+    /// The grid is the points' bounding box.
+    pub fn from_points<T: TryInto<i64>>(points: impl IntoIterator<Item = (T, T)>) -> Self {
+        let points: Vec<(i64, i64)> = points.into_iter()
+            .map(|(x, y)| (x.try_into().ok().unwrap(), y.try_into().ok().unwrap()))
+            .collect();
+        let Some(min_x) = points.iter().map(|p| p.0).min() else { return Self::new(0, 0, vec![]) };
+        let max_x = points.iter().map(|p| p.0).max().unwrap();
+        let min_y = points.iter().map(|p| p.1).min().unwrap();
+        let max_y = points.iter().map(|p| p.1).max().unwrap();
+
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+        let mut pixels = vec![false; width * height];
+        for (x, y) in points {
+            pixels[(y - min_y) as usize * width + (x - min_x) as usize] = true;
+        }
+        Self::new(width, height, pixels)
+    }
+
+    /// Parses `#` as lit and anything else (`.`, spaces) as unlit
+    pub fn from_art(art: &str) -> Self {
+        Self::from_rows(art.trim_matches('\n').lines().map(|line| line.chars().map(|c| c == '#')))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.pixels[y * self.width + x]
+    }
+
+    fn column_empty(&self, x: usize) -> bool {
+        (0..self.height).all(|y| !self.get(x, y))
+    }
+    fn row_empty(&self, y: usize) -> bool {
+        (0..self.width).all(|x| !self.get(x, y))
+    }
+
+    /// Cuts off the unlit border
+    pub fn trimmed(&self) -> Self {
+        let xs: Vec<_> = (0..self.width).filter(|&x| !self.column_empty(x)).collect();
+        let ys: Vec<_> = (0..self.height).filter(|&y| !self.row_empty(y)).collect();
+        let (Some(&x0), Some(&x1), Some(&y0), Some(&y1)) = (xs.first(), xs.last(), ys.first(), ys.last()) else {
+            return Self::new(0, 0, vec![]);
+        };
+        Self::from_rows((y0..=y1).map(|y| (x0..=x1).map(move |x| self.get(x, y))))
+    }
+
+    /// Reads the letters in the grid, if it's written in one of the fonts AoC
+    /// uses (4x6 or 6x10) and every letter is recognised
+    pub fn ocr(&self) -> Option<String> {
+        let grid = &self.trimmed();
+        let font = match grid.height {
+            6 => FONT_6,
+            10 => FONT_10,
+            _ => return None,
+        };
+
+        let mut letters = String::new();
+        let mut x = 0;
+        while x < grid.width {
+            if grid.column_empty(x) {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < grid.width && !grid.column_empty(x) {
+                x += 1;
+            }
+            let glyph = Self::from_rows((0..grid.height).map(|y| (start..x).map(move |x| grid.get(x, y))));
+            let &(letter, _) = font.iter().find(|(_, art)| Self::from_art(art) == glyph)?;
+            letters.push(letter);
+        }
+        Some(letters)
+    }
+}
+
+impl Display for PixelGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..self.width {
+                write!(f, "{}", if self.get(x, y) { '#' } else { '.' })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+const FONT_6: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const FONT_10: &[(char, &str)] = &[
+    ('A', "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#"),
+    ('B', "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####."),
+    ('C', ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####."),
+    ('E', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('F', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('G', ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#"),
+    ('H', "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#"),
+    ('J', "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###.."),
+    ('K', "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#"),
+    ('L', "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('N', "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#"),
+    ('P', "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('R', "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#"),
+    ('X', "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#"),
+    ('Z', "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######"),
+];
+
+#[test]
+fn test_ocr() {
+    let grid = PixelGrid::from_art("
+.##..###...##.
+#..#.#..#.#..#
+#..#.###..#...
+####.#..#.#...
+#..#.#..#.#..#
+#..#.###...##.
+");
+    assert_eq!(grid.ocr().as_deref(), Some("ABC"));
+
+    let padded = PixelGrid::from_points(
+        FONT_10.iter()
+            .find(|(letter, _)| *letter == 'X')
+            .unwrap().1
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| line.match_indices('#').map(move |(x, _)| (x as i32 - 3, y as i32 - 7))),
+    );
+    assert_eq!(padded.ocr().as_deref(), Some("X"));
+    assert_eq!(PixelGrid::from_art("#.#\n.#.").ocr(), None);
+}
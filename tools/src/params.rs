@@ -0,0 +1,183 @@
+use std::str::FromStr;
+
+use crate::answers::Answer;
+
+pub use toml::{Table, Value};
+
+/// Which values a day's `Params` should take
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Preset<'a> {
+    /// The real input's, i.e. `Default`
+    Real,
+    /// `test.txt`'s
+    Test,
+    /// `test.txt`'s, with an example's `params` table on top
+    Example(&'a Table),
+}
+
+impl Preset<'_> {
+    pub fn for_source(source: &crate::input::InputSource) -> Self {
+        match source {
+            crate::input::InputSource::Test => Self::Test,
+            _ => Self::Real,
+        }
+    }
+}
+
+/// Constants a day hard-codes for the real input but that its examples use
+/// other values for, e.g. a grid size. Usually generated by `params!`.
+pub trait Params: Default {
+    fn test() -> Self {
+        Self::default()
+    }
+
+    fn set(&mut self, key: &str, value: &Value) -> Result<(), String>;
+
+    fn from_preset(preset: &Preset) -> Result<Self, String> {
+        match preset {
+            Preset::Real => Ok(Self::default()),
+            Preset::Test => Ok(Self::test()),
+            Preset::Example(table) => {
+                let mut params = Self::test();
+                for (key, value) in table.iter() {
+                    params.set(key, value)?;
+                }
+                Ok(params)
+            },
+        }
+    }
+}
+
+/// Days without parameters
+impl Params for () {
+    fn set(&mut self, key: &str, _value: &Value) -> Result<(), String> {
+        Err(format!("Unknown parameter `{key}` (the day has no parameters)"))
+    }
+}
+
+pub fn parse_value<T: FromStr>(key: &str, value: &Value) -> Result<T, String> {
+    let text = match value {
+        Value::String(s) => s.clone(),
+        Value::Integer(i) => i.to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::Boolean(b) => b.to_string(),
+        v => return Err(format!("Parameter `{key}` can't be a {}", v.type_str())),
+    };
+    text.parse().map_err(|_| format!("Invalid value `{text}` for parameter `{key}`"))
+}
+
+/// Marks part functions that take only the input
+pub struct InputOnly;
+/// Marks part functions that take the input and the day's `Params`
+pub struct WithParams;
+
+/// Lets `aoc_sol!` and the benches call `part1(input)` and
+/// `part1(input, &params)` alike
+pub trait Part<P, Marker> {
+    type Output;
+    fn call(&self, input: &str, params: &P) -> Self::Output;
+}
+
+impl<F: Fn(&str) -> T, T, P> Part<P, InputOnly> for F {
+    type Output = T;
+    fn call(&self, input: &str, _params: &P) -> T {
+        self(input)
+    }
+}
+
+impl<F: Fn(&str, &P) -> T, T, P> Part<P, WithParams> for F {
+    type Output = T;
+    fn call(&self, input: &str, params: &P) -> T {
+        self(input, params)
+    }
+}
+
+/// A day's `Params`, built once from a `Preset` and shared by all its parts
+/// so that building them isn't part of any part's timing
+pub struct DayParams(Box<dyn std::any::Any>);
+
+impl DayParams {
+    pub fn from_preset<P: Params + 'static>(preset: &Preset) -> Result<Self, String> {
+        Ok(Self(Box::new(P::from_preset(preset)?)))
+    }
+}
+
+/// `aoc_sol!`'s `AOC_PARAMS`, building the `DayParams` its `AOC_PARTS` take
+pub type ParamsFn = fn(&Preset) -> Result<DayParams, String>;
+/// One of `aoc_sol!`'s `AOC_PARTS`
+pub type PartFn = fn(&str, &DayParams) -> Answer;
+
+/// Runs a part with the day's params, for `aoc_sol!`'s `AOC_PARTS`
+pub fn call_with_params<P: 'static, M, F: Part<P, M>>(part: &F, input: &str, params: &DayParams) -> Answer
+where F::Output: Into<Answer> {
+    let params = params.0.downcast_ref::<P>().expect("DayParams built for a different day");
+    part.call(input, params).into()
+}
+
+/// Declares a day's `Params`: each field has the real input's value and
+/// optionally a different one for `test.txt`
+///
+/// ```ignore
+/// aoc_tools::params! {
+///     width: u8 = 101, test 11;
+///     steps: u8 = 100;
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    ($($field:ident: $type:ty = $real:expr $(, test $test:expr)?);+ $(;)?) => {
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Params {
+            $(pub $field: $type),+
+        }
+        impl Default for Params {
+            fn default() -> Self {
+                Self { $($field: $real),+ }
+            }
+        }
+        impl $crate::params::Params for Params {
+            fn test() -> Self {
+                Self { $($field: [$($test,)? $real][0]),+ }
+            }
+            fn set(&mut self, key: &str, value: &$crate::params::Value) -> Result<(), String> {
+                match key {
+                    $(stringify!($field) => self.$field = $crate::params::parse_value(key, value)?,)+
+                    _ => return Err(format!("Unknown parameter `{key}`")),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+#[test]
+fn test_params() {
+    #[derive(Debug, Default, PartialEq)]
+    struct Grid { width: u8, steps: u32 }
+    impl Params for Grid {
+        fn test() -> Self {
+            Self { width: 11, steps: 0 }
+        }
+        fn set(&mut self, key: &str, value: &Value) -> Result<(), String> {
+            match key {
+                "width" => self.width = parse_value(key, value)?,
+                "steps" => self.steps = parse_value(key, value)?,
+                _ => return Err(format!("Unknown parameter `{key}`")),
+            }
+            Ok(())
+        }
+    }
+
+    let table: Table = "steps = 12".parse().unwrap();
+    assert_eq!(Grid::from_preset(&Preset::Example(&table)), Ok(Grid { width: 11, steps: 12 }));
+    assert_eq!(Grid::from_preset(&Preset::Real), Ok(Grid::default()));
+    assert!(Grid::from_preset(&Preset::Example(&"width = 300".parse().unwrap())).is_err());
+    assert!(<()>::from_preset(&Preset::Example(&table)).is_err());
+
+    fn part1(input: &str, grid: &Grid) -> usize { input.len() * grid.width as usize }
+    fn part2(input: &str) -> usize { input.len() }
+    let params = DayParams::from_preset::<Grid>(&Preset::Test).unwrap();
+    assert_eq!(call_with_params(&part1, "ab", &params), Answer::Int(22));
+    assert_eq!(call_with_params::<Grid, _, _>(&part2, "ab", &params), Answer::Int(2));
+    assert!(DayParams::from_preset::<Grid>(&Preset::Example(&"width = 300".parse().unwrap())).is_err());
+}
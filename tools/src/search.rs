@@ -0,0 +1,274 @@
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::{BuildHasher, Hash};
+use std::ops::Add;
+
+/// What a search knows about a state it has reached
+#[derive(Debug, Clone, PartialEq)]
+pub struct Visit<S, C> {
+    pub cost: C,
+    /// Only recorded if the search keeps paths
+    pub prev: Option<S>,
+    settled: bool,
+}
+
+/// Where a search records the states it has reached. Implemented for every
+/// `HashMap` (so `FastMap` too) and for `DenseMap`.
+pub trait StateMap<S, V> {
+    fn get(&self, state: &S) -> Option<&V>;
+    fn get_mut(&mut self, state: &S) -> Option<&mut V>;
+    fn insert(&mut self, state: S, value: V);
+}
+
+impl<S: Hash + Eq, V, B: BuildHasher> StateMap<S, V> for HashMap<S, V, B> {
+    fn get(&self, state: &S) -> Option<&V> {
+        HashMap::get(self, state)
+    }
+    fn get_mut(&mut self, state: &S) -> Option<&mut V> {
+        HashMap::get_mut(self, state)
+    }
+    fn insert(&mut self, state: S, value: V) {
+        HashMap::insert(self, state, value);
+    }
+}
+
+/// A `StateMap` for states that can be numbered from 0, e.g. grid positions.
+/// Skips hashing, but `index` must give every state a different number below `len`.
+#[derive(Debug, Clone)]
+pub struct DenseMap<V, F> {
+    values: Vec<Option<V>>,
+    index: F,
+}
+
+impl<V, F> DenseMap<V, F> {
+    pub fn new(len: usize, index: F) -> Self {
+        Self {
+            values: std::iter::repeat_with(|| None).take(len).collect(),
+            index,
+        }
+    }
+}
+
+impl<S, V, F: Fn(&S) -> usize> StateMap<S, V> for DenseMap<V, F> {
+    fn get(&self, state: &S) -> Option<&V> {
+        self.values.get((self.index)(state))?.as_ref()
+    }
+    fn get_mut(&mut self, state: &S) -> Option<&mut V> {
+        self.values.get_mut((self.index)(state))?.as_mut()
+    }
+    fn insert(&mut self, state: S, value: V) {
+        let i = (self.index)(&state);
+        let len = self.values.len();
+        *self.values
+            .get_mut(i)
+            .unwrap_or_else(|| panic!("State index {i} out of range for a DenseMap of {len}")) = Some(value);
+    }
+}
+
+/// A search over states generated as they're reached, rather than stored in a
+/// `Graph`. `bfs`, `dijkstra` and `astar` cover the usual case.
+#[derive(Debug, Clone)]
+pub struct Search<M> {
+    visited: M,
+    keep_paths: bool,
+}
+
+impl<S, C> Search<HashMap<S, Visit<S, C>>> {
+    pub fn new() -> Self {
+        Self::with_visited(HashMap::new())
+    }
+}
+
+impl<S, C> Default for Search<HashMap<S, Visit<S, C>>> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M> Search<M> {
+    /// Searches with a different `StateMap`, e.g. a `FastMap` or a `DenseMap`.
+    /// It should be empty.
+    pub fn with_visited(visited: M) -> Self {
+        Self { visited, keep_paths: false }
+    }
+    /// Records how each state was reached, for `Searched::path`
+    pub fn keep_paths(mut self) -> Self {
+        self.keep_paths = true;
+        self
+    }
+
+    /// Every step costs 1
+    pub fn bfs<S: Clone, I: IntoIterator<Item = S>>(
+        mut self,
+        start: S,
+        mut successors: impl FnMut(&S) -> I,
+        mut is_goal: impl FnMut(&S) -> bool,
+    ) -> Searched<S, usize, M>
+    where M: StateMap<S, Visit<S, usize>> {
+        self.visited.insert(start.clone(), Visit { cost: 0, prev: None, settled: true });
+        let mut queue = VecDeque::from([(0, start)]);
+        while let Some((cost, state)) = queue.pop_front() {
+            if is_goal(&state) {
+                return self.found(Some((cost, state)));
+            }
+            for next in successors(&state) {
+                if self.visited.get(&next).is_some() { continue }
+                let prev = self.keep_paths.then(|| state.clone());
+                self.visited.insert(next.clone(), Visit { cost: cost + 1, prev, settled: true });
+                queue.push_back((cost + 1, next));
+            }
+        }
+        self.found(None)
+    }
+
+    /// `successors` gives each next state with the cost of moving to it, which
+    /// must not be negative. `C::default()` must be a cost of 0.
+    pub fn dijkstra<S: Clone, C: Ord + Copy + Default + Add<Output = C>, I: IntoIterator<Item = (S, C)>>(
+        self,
+        start: S,
+        successors: impl FnMut(&S) -> I,
+        is_goal: impl FnMut(&S) -> bool,
+    ) -> Searched<S, C, M>
+    where M: StateMap<S, Visit<S, C>> {
+        self.astar(start, successors, |_| C::default(), is_goal)
+    }
+
+    /// `dijkstra`, guided by `heuristic`'s estimate of the cost left to a goal.
+    /// It must never drop by more than the cost of the move made, so it never
+    /// overestimates.
+    pub fn astar<S: Clone, C: Ord + Copy + Default + Add<Output = C>, I: IntoIterator<Item = (S, C)>>(
+        mut self,
+        start: S,
+        mut successors: impl FnMut(&S) -> I,
+        mut heuristic: impl FnMut(&S) -> C,
+        mut is_goal: impl FnMut(&S) -> bool,
+    ) -> Searched<S, C, M>
+    where M: StateMap<S, Visit<S, C>> {
+        self.visited.insert(start.clone(), Visit { cost: C::default(), prev: None, settled: false });
+        let mut heap = BinaryHeap::from([Queued { priority: heuristic(&start), distance: C::default(), id: start }]);
+        while let Some(Queued { distance: cost, id: state, .. }) = heap.pop() {
+            let visit = self.visited
+                .get_mut(&state)
+                .expect("Invariant violated: queued state was never visited");
+            if visit.settled { continue }
+            visit.settled = true;
+            if is_goal(&state) {
+                return self.found(Some((cost, state)));
+            }
+
+            for (next, step_cost) in successors(&state) {
+                let new_cost = cost + step_cost;
+                if let Some(visit) = self.visited.get(&next) {
+                    if visit.settled || visit.cost <= new_cost { continue }
+                }
+                let prev = self.keep_paths.then(|| state.clone());
+                self.visited.insert(next.clone(), Visit { cost: new_cost, prev, settled: false });
+                heap.push(Queued { priority: new_cost + heuristic(&next), distance: new_cost, id: next });
+            }
+        }
+        self.found(None)
+    }
+
+    fn found<S, C>(self, goal: Option<(C, S)>) -> Searched<S, C, M> {
+        Searched { goal, visited: self.visited, keep_paths: self.keep_paths }
+    }
+}
+
+/// The outcome of a `Search`
+#[derive(Debug, Clone)]
+pub struct Searched<S, C, M> {
+    /// The cost of the first goal state reached, and that state
+    pub goal: Option<(C, S)>,
+    pub visited: M,
+    keep_paths: bool,
+}
+
+impl<S: Clone, C: Copy, M: StateMap<S, Visit<S, C>>> Searched<S, C, M> {
+    /// The cheapest cost of reaching `state`, if the search got far enough to know it
+    pub fn cost_to(&self, state: &S) -> Option<C> {
+        self.visited.get(state).filter(|visit| visit.settled).map(|visit| visit.cost)
+    }
+    /// The path to the goal, starting at the start
+    pub fn path(&self) -> Option<Vec<S>> {
+        let (_, goal) = self.goal.as_ref()?;
+        self.path_to(goal)
+    }
+    /// The cheapest path to `state`, if the search got far enough to know it
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        assert!(self.keep_paths, "Paths are only available if the search was told to keep them");
+        self.cost_to(state)?;
+        let mut path = vec![state.clone()];
+        while let Some(prev) = self.visited.get(path.last().unwrap()).and_then(|visit| visit.prev.clone()) {
+            path.push(prev);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// The number of steps to the first goal state reached, and that state
+pub fn bfs<S: Hash + Eq + Clone, I: IntoIterator<Item = S>>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(usize, S)> {
+    Search::new().bfs(start, successors, is_goal).goal
+}
+
+/// The cost of the cheapest goal state, and that state. See `Search::dijkstra`.
+pub fn dijkstra<S: Hash + Eq + Clone, C: Ord + Copy + Default + Add<Output = C>, I: IntoIterator<Item = (S, C)>>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, S)> {
+    Search::new().dijkstra(start, successors, is_goal).goal
+}
+
+/// The cost of the cheapest goal state, and that state. See `Search::astar`.
+pub fn astar<S: Hash + Eq + Clone, C: Ord + Copy + Default + Add<Output = C>, I: IntoIterator<Item = (S, C)>>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, S)> {
+    Search::new().astar(start, successors, heuristic, is_goal).goal
+}
+
+/// An entry in a search's heap. Compares backwards so the lowest priority pops
+/// first.
+pub(crate) struct Queued<Id, E> {
+    pub(crate) priority: E,
+    pub(crate) distance: E,
+    pub(crate) id: Id,
+}
+impl<Id, E: Ord> PartialEq for Queued<Id, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+impl<Id, E: Ord> Eq for Queued<Id, E> {}
+impl<Id, E: Ord> PartialOrd for Queued<Id, E> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<Id, E: Ord> Ord for Queued<Id, E> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+#[test]
+fn test_search() {
+    // Reach 10 from 1 by doubling (cost 1) or adding one (cost 3)
+    let successors = |&n: &u32| [(n * 2, 1), (n + 1, 3)].into_iter().filter(|&(n, _)| n <= 20);
+    assert_eq!(dijkstra(1, successors, |&n| n == 10), Some((6, 10)));
+    assert_eq!(astar(1, successors, |&n| if n > 10 { 100 } else { 0 }, |&n| n == 10), Some((6, 10)));
+    assert_eq!(bfs(1, |&n| [n * 2, n + 1], |&n| n == 10), Some((4, 10)));
+    assert_eq!(dijkstra(1, successors, |&n| n == 21), None);
+
+    let searched = Search::with_visited(DenseMap::new(21, |&n: &u32| n as usize))
+        .keep_paths()
+        .dijkstra(1, successors, |&n| n == 10);
+    assert_eq!(searched.path(), Some(vec![1, 2, 4, 5, 10]));
+    assert_eq!(searched.cost_to(&4), Some(2));
+}
@@ -0,0 +1,181 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::Index;
+
+/// Positions a `SparseGrid` can be indexed by. `pos!` and `pos3!` implement it,
+/// and `[i64; N]` covers any other number of dimensions.
+pub trait SparsePos: Copy + 'static {
+    const DIMENSIONS: usize;
+    fn coord(&self, axis: usize) -> i64;
+    fn from_coords(coord: impl FnMut(usize) -> i64) -> Self;
+
+    /// Every position touching this one, diagonals included
+    fn neighbors(self) -> impl Iterator<Item = Self> {
+        let count = 3usize.pow(Self::DIMENSIONS as u32);
+        // Each offset is a base 3 number with a digit per axis, and the middle one is no offset
+        (0..count)
+            .filter(move |&i| i != count / 2)
+            .map(move |i| Self::from_coords(|axis| self.coord(axis) + (i / 3usize.pow(axis as u32) % 3) as i64 - 1))
+    }
+}
+
+impl<const N: usize> SparsePos for [i64; N] {
+    const DIMENSIONS: usize = N;
+    fn coord(&self, axis: usize) -> i64 {
+        self[axis]
+    }
+    fn from_coords(coord: impl FnMut(usize) -> i64) -> Self {
+        std::array::from_fn(coord)
+    }
+}
+
+/// An unbounded grid where every cell not set is `background`, e.g. for
+/// cellular automata that spread forever
+#[derive(Debug, Clone)]
+pub struct SparseGrid<P, T> {
+    /// Only cells that differ from the background
+    cells: HashMap<P, T>,
+    background: T,
+}
+
+impl<P: SparsePos + Hash + Eq, T: Clone + PartialEq> SparseGrid<P, T> {
+    pub fn new(background: T) -> Self {
+        Self { cells: HashMap::new(), background }
+    }
+
+    /// One cell per character, at `(x, y)` with any other coordinates 0 and
+    /// y going down. Blank lines are skipped.
+    pub fn parse(input: &str, background: T, mut cell: impl FnMut(char) -> T) -> Self {
+        assert!(P::DIMENSIONS >= 2, "Text needs at least 2 dimensions");
+        let mut grid = Self::new(background);
+        for (y, line) in input.lines().filter(|line| !line.trim().is_empty()).enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let pos = P::from_coords(|axis| match axis {
+                    0 => x as i64,
+                    1 => y as i64,
+                    _ => 0,
+                });
+                grid.set(pos, cell(c));
+            }
+        }
+        grid
+    }
+
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+    pub fn get(&self, pos: P) -> &T {
+        self.cells.get(&pos).unwrap_or(&self.background)
+    }
+    pub fn set(&mut self, pos: P, value: T) {
+        if value == self.background {
+            self.cells.remove(&pos);
+        } else {
+            self.cells.insert(pos, value);
+        }
+    }
+
+    /// The number of cells that differ from the background
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+    /// The cells that differ from the background, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (P, &T)> + '_ {
+        self.cells.iter().map(|(&pos, cell)| (pos, cell))
+    }
+    /// Of the cells that differ from the background
+    pub fn count(&self, mut pred: impl FnMut(&T) -> bool) -> usize {
+        self.cells.values().filter(|cell| pred(cell)).count()
+    }
+
+    /// The lowest and highest corners of the box around every cell that differs
+    /// from the background, or `None` if there are none
+    pub fn bounds(&self) -> Option<(P, P)> {
+        let mut positions = self.cells.keys();
+        let first = *positions.next()?;
+        let (mut min, mut max) = (vec![0; P::DIMENSIONS], vec![0; P::DIMENSIONS]);
+        for axis in 0..P::DIMENSIONS {
+            (min[axis], max[axis]) = (first.coord(axis), first.coord(axis));
+        }
+        for pos in positions {
+            for axis in 0..P::DIMENSIONS {
+                min[axis] = min[axis].min(pos.coord(axis));
+                max[axis] = max[axis].max(pos.coord(axis));
+            }
+        }
+        Some((P::from_coords(|axis| min[axis]), P::from_coords(|axis| max[axis])))
+    }
+
+    pub fn neighbors(&self, pos: P) -> impl Iterator<Item = (P, &T)> + '_ {
+        pos.neighbors().map(|neighbor| (neighbor, self.get(neighbor)))
+    }
+    pub fn count_neighbors(&self, pos: P, mut pred: impl FnMut(&T) -> bool) -> usize {
+        self.neighbors(pos).filter(|(_, cell)| pred(cell)).count()
+    }
+
+    /// One generation of a Life-like automaton, where every cell becomes
+    /// `rule(cell, neighbours)` at once. The background follows the rule too,
+    /// as if surrounded by more background, so it can flip between generations.
+    pub fn step(&self, mut rule: impl FnMut(&T, &[&T]) -> T) -> Self {
+        let background_neighbors = P::from_coords(|_| 0).neighbors().count();
+        let background = rule(&self.background, &vec![&self.background; background_neighbors]);
+
+        // Anything further from a non-background cell stays background
+        let mut to_update: HashSet<P> = HashSet::with_capacity(self.cells.len() * (background_neighbors + 1));
+        for &pos in self.cells.keys() {
+            to_update.insert(pos);
+            to_update.extend(pos.neighbors());
+        }
+
+        let mut next = Self::new(background);
+        let mut neighbors = Vec::with_capacity(background_neighbors);
+        for pos in to_update {
+            neighbors.clear();
+            neighbors.extend(self.neighbors(pos).map(|(_, cell)| cell));
+            next.set(pos, rule(self.get(pos), &neighbors));
+        }
+        next
+    }
+}
+
+impl<P: SparsePos + Hash + Eq, T> Index<P> for SparseGrid<P, T> {
+    type Output = T;
+    fn index(&self, pos: P) -> &T {
+        self.cells.get(&pos).unwrap_or(&self.background)
+    }
+}
+
+#[test]
+fn test_sparse_grid() {
+    let life = |&cell: &bool, neighbors: &[&bool]| {
+        let alive = neighbors.iter().filter(|&&&n| n).count();
+        alive == 3 || (cell && alive == 2)
+    };
+    let input = ".#.\n..#\n###\n";
+
+    let mut cubes = SparseGrid::<[i64; 3], bool>::parse(input, false, |c| c == '#');
+    assert_eq!(cubes.bounds(), Some(([0, 0, 0], [2, 2, 0])));
+    assert_eq!(cubes.count_neighbors([1, 1, 0], |&cell| cell), 5);
+    cubes = cubes.step(life);
+    assert_eq!(cubes.bounds(), Some(([0, 1, -1], [2, 3, 1])));
+    for _ in 1..6 {
+        cubes = cubes.step(life);
+    }
+    assert_eq!(cubes.len(), 112);
+
+    let mut hypercubes = SparseGrid::<[i64; 4], bool>::parse(input, false, |c| c == '#');
+    for _ in 0..6 {
+        hypercubes = hypercubes.step(life);
+    }
+    assert_eq!(hypercubes.count(|&cell| cell), 848);
+
+    // A rule that lights up empty space flips the background
+    let mut image = SparseGrid::<[i64; 2], bool>::new(false);
+    image.set([0, 0], true);
+    image = image.step(|&cell, neighbors| !cell && neighbors.iter().all(|&&n| !n));
+    assert!(*image.background() && image[[5, 5]] && !image[[0, 0]]);
+    assert_eq!(image.len(), 9);
+}
@@ -0,0 +1,216 @@
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Parts slower than this get flagged in the text summary
+pub const SLOW_THRESHOLD: Duration = Duration::from_millis(10);
+
+pub const CSV_HEADER: &str = "year,day,part,median_ns,min_ns,iterations,commit,answer_hash";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimingRecord {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub median_ns: u64,
+    pub min_ns: u64,
+    pub iterations: u32,
+    pub commit: Option<String>,
+    pub answer_hash: String,
+}
+
+impl TimingRecord {
+    pub fn new(year: u16, day: u8, part: u8, timing: &Timing, answer: &str, commit: Option<String>) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            median_ns: timing.median.as_nanos() as u64,
+            min_ns: timing.min.as_nanos() as u64,
+            iterations: timing.iterations,
+            commit,
+            answer_hash: answer_hash(answer),
+        }
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+
+    pub fn to_csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{}",
+            self.year,
+            self.day,
+            self.part,
+            self.median_ns,
+            self.min_ns,
+            self.iterations,
+            self.commit.as_deref().unwrap_or(""),
+            self.answer_hash,
+        )
+    }
+
+    pub fn from_csv_row(row: &str) -> Result<Self, String> {
+        let fields: Vec<_> = row.trim().split(',').collect();
+        let &[year, day, part, median_ns, min_ns, iterations, commit, answer_hash] = fields.as_slice() else {
+            return Err(format!("Expected 8 fields, got {}", fields.len()));
+        };
+        fn num<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+            value.parse().map_err(|_| format!("Invalid {name} `{value}`"))
+        }
+        Ok(Self {
+            year: num("year", year)?,
+            day: num("day", day)?,
+            part: num("part", part)?,
+            median_ns: num("median_ns", median_ns)?,
+            min_ns: num("min_ns", min_ns)?,
+            iterations: num("iterations", iterations)?,
+            commit: Some(commit.to_string()).filter(|commit| !commit.is_empty()),
+            answer_hash: answer_hash.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub median: Duration,
+    pub min: Duration,
+    pub iterations: u32,
+}
+
+/// Runs `f` `iterations` times (at least once), returning the last output
+pub fn measure<T>(iterations: u32, mut f: impl FnMut() -> T) -> (T, Timing) {
+    let iterations = iterations.max(1);
+    let mut times = Vec::with_capacity(iterations as usize);
+    let mut output = None;
+    for _ in 0..iterations {
+        let start = Instant::now();
+        output = Some(f());
+        times.push(start.elapsed());
+    }
+    times.sort();
+    let timing = Timing { median: times[times.len() / 2], min: times[0], iterations };
+    (output.unwrap(), timing)
+}
+
+pub fn answer_hash(answer: &str) -> String {
+    format!("{:016x}", xxhash_rust::xxh3::xxh3_64(answer.trim().as_bytes()))
+}
+
+/// The short hash of the current `HEAD`, if git is available
+pub fn git_commit() -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() { return None }
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+}
+
+fn is_csv(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+}
+
+/// Appends records to `path`, as CSV for `.csv` files and JSON lines otherwise
+pub fn append_records(path: &Path, records: &[TimingRecord]) -> std::io::Result<()> {
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    let csv = is_csv(path);
+    if csv && file.metadata()?.len() == 0 {
+        writeln!(file, "{CSV_HEADER}")?;
+    }
+    for record in records {
+        if csv {
+            writeln!(file, "{}", record.to_csv_row())?;
+        } else {
+            writeln!(file, "{}", serde_json::to_string(record).map_err(std::io::Error::other)?)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn load_records(path: &Path) -> Result<Vec<TimingRecord>, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+    let csv = is_csv(path);
+    contents.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter(|(_, line)| !(csv && line.trim() == CSV_HEADER))
+        .map(|(i, line)| {
+            let record = if csv {
+                TimingRecord::from_csv_row(line)
+            } else {
+                serde_json::from_str(line).map_err(|e| e.to_string())
+            };
+            record.map_err(|e| format!("{}:{}: {e}", path.display(), i + 1))
+        })
+        .collect()
+}
+
+/// Renders a `YYYY-times.txt` style summary of `year`, using the latest
+/// record for each part
+pub fn summary(records: &[TimingRecord], year: u16, slow_threshold: Duration) -> String {
+    let mut latest = std::collections::BTreeMap::new();
+    for record in records.iter().filter(|record| record.year == year) {
+        latest.insert((record.day, record.part), record);
+    }
+
+    let mut output = String::new();
+    let mut slow = vec![];
+    let mut total = Duration::ZERO;
+    for (&(day, part), record) in &latest {
+        let median = record.median();
+        total += median;
+        let micros = median.as_nanos() as f64 / 1000.0;
+        write!(output, "d{day:02}p{part}:{micros:>10.3}µs").unwrap();
+        if median > slow_threshold {
+            write!(output, " !!!!!").unwrap();
+            slow.push((median, day, part));
+        }
+        writeln!(output).unwrap();
+    }
+
+    if !slow.is_empty() {
+        slow.sort_by(|a, b| b.cmp(a));
+        let worst: Vec<_> = slow.iter().map(|(_, day, part)| format!("{day:02}.{part}")).collect();
+        write!(output, "\nWorst: {}\n", worst.join(", ")).unwrap();
+    }
+    write!(output, "\nAll of {year}: {:.2}ms\n", total.as_nanos() as f64 / 1_000_000.0).unwrap();
+    output
+}
+
+#[test]
+fn test_timing_summary() {
+    let record = |day, part, median_ns| TimingRecord {
+        year: 2024, day, part, median_ns, min_ns: median_ns, iterations: 1,
+        commit: None,
+        answer_hash: answer_hash("0"),
+    };
+    let records = [
+        record(1, 1, 14_467),
+        record(6, 2, 10_000_000),
+        record(6, 2, 13_331_000),
+        record(22, 2, 43_108_000),
+        record(1, 2, 40_828),
+    ];
+    assert_eq!(
+        summary(&records, 2024, SLOW_THRESHOLD),
+        "\
+d01p1:    14.467µs
+d01p2:    40.828µs
+d06p2: 13331.000µs !!!!!
+d22p2: 43108.000µs !!!!!
+
+Worst: 22.2, 06.2
+
+All of 2024: 56.49ms
+",
+    );
+
+    let csv_row = records[2].to_csv_row();
+    assert_eq!(TimingRecord::from_csv_row(&csv_row), Ok(records[2].clone()));
+}
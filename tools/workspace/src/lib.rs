@@ -0,0 +1,226 @@
+use std::path::Path;
+
+/// A `YYYY/dayDD` member of the workspace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayCrate {
+    pub year: u16,
+    pub day: u8,
+    /// Relative to the workspace, e.g. `2024/day06`
+    pub path: String,
+    /// The parts listed in its `aoc_sol!`, or why it has none
+    pub parts: Result<Vec<String>, String>,
+}
+
+impl DayCrate {
+    /// The package name, e.g. `day06-2024`
+    pub fn name(&self) -> String {
+        format!("day{:02}-{}", self.day, self.year)
+    }
+    /// The library's name in Rust, e.g. `day06_2024`
+    pub fn module(&self) -> String {
+        self.name().replace('-', "_")
+    }
+    pub fn has_part(&self, part: &str) -> bool {
+        self.parts.as_ref().is_ok_and(|parts| parts.iter().any(|p| p == part))
+    }
+}
+
+pub fn read_manifest(path: &Path) -> Result<toml::Table, String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| format!("Could not read {}: {e}", path.display()))?
+        .parse()
+        .map_err(|e| format!("Invalid {}: {e}", path.display()))
+}
+
+/// Every day crate among the workspace's members, sorted by year and day
+pub fn day_crates(workspace_dir: &Path) -> Result<Vec<DayCrate>, String> {
+    let manifest = read_manifest(&workspace_dir.join("Cargo.toml"))?;
+    let members = manifest.get("workspace")
+        .and_then(|workspace| workspace.get("members"))
+        .and_then(|members| members.as_array())
+        .ok_or("The workspace has no members")?;
+
+    let mut days = vec![];
+    for path in members.iter().filter_map(|member| member.as_str()) {
+        let Some((year, day)) = parse_day_path(path) else { continue };
+        let lib_path = workspace_dir.join(path).join("src/lib.rs");
+        let parts = match std::fs::read_to_string(&lib_path) {
+            Ok(lib) => aoc_sol_parts(&lib).ok_or_else(|| "library has no `aoc_sol!`".to_string()),
+            Err(_) => Err("no library target (main.rs only)".to_string()),
+        };
+        days.push(DayCrate { year, day, path: path.to_string(), parts });
+    }
+    days.sort_by_key(|day| (day.year, day.day));
+    Ok(days)
+}
+
+/// `2024/day06` -> `(2024, 6)`
+pub fn parse_day_path(path: &str) -> Option<(u16, u8)> {
+    let (year, day) = path.split_once('/')?;
+    Some((year.parse().ok()?, day.strip_prefix("day")?.parse().ok()?))
+}
+
+/// The part functions named in a library's `aoc_sol!`, e.g. `part1, part2`
+/// from `aoc_sol!(day06 2024: part1, part2; params: Params)`
+pub fn aoc_sol_parts(lib: &str) -> Option<Vec<String>> {
+    let start = lib.match_indices("aoc_sol!").map(|(i, _)| i).find(|&i| {
+        let line_start = lib[..i].rfind('\n').map_or(0, |newline| newline + 1);
+        !lib[line_start..i].trim_start().starts_with("//")
+    })?;
+    let invocation = lib[start + "aoc_sol!".len()..].trim_start();
+    let close = match invocation.chars().next()? {
+        '(' => ')',
+        '{' => '}',
+        '[' => ']',
+        _ => return None,
+    };
+    let body = &invocation[1..invocation.find(close)?];
+    let (_, rest) = body.split_once(':')?;
+    let parts: Vec<String> = rest.split(';')
+        .next()?
+        .split(',')
+        .map(|part| part.trim().to_string())
+        .filter(|part| !part.is_empty())
+        .collect();
+    (!parts.is_empty()).then_some(parts)
+}
+
+/// Marks the dependencies `sync_dependencies` writes
+pub const GENERATED_MARKER: &str = "# Day crates, generated by `cargo aoc-workspace sync`";
+
+/// Rewrites the day crate dependencies in the manifest at `manifest_path` to
+/// be every day crate with a library, newest year first. `to_workspace` is
+/// the path from the manifest's directory back to the workspace. Returns
+/// whether anything changed.
+pub fn sync_dependencies(manifest_path: &Path, to_workspace: &str, days: &[DayCrate]) -> Result<bool, String> {
+    let contents = std::fs::read_to_string(manifest_path)
+        .map_err(|e| format!("Could not read {}: {e}", manifest_path.display()))?;
+    let new_contents = with_dependencies(&contents, to_workspace, days)
+        .map_err(|e| format!("{}: {e}", manifest_path.display()))?;
+    if new_contents == contents {
+        return Ok(false);
+    }
+    std::fs::write(manifest_path, new_contents)
+        .map_err(|e| format!("Could not write {}: {e}", manifest_path.display()))?;
+    Ok(true)
+}
+
+fn with_dependencies(manifest: &str, to_workspace: &str, days: &[DayCrate]) -> Result<String, String> {
+    let is_day_dependency = |line: &str| line.split_once(" = ").is_some_and(|(name, _)| {
+        name.strip_prefix("day").and_then(|name| name.split_once('-')).is_some_and(|(day, year)| {
+            day.parse::<u8>().is_ok() && year.parse::<u16>().is_ok()
+        })
+    });
+    let lines: Vec<&str> = manifest.lines()
+        .filter(|&line| line != GENERATED_MARKER && !is_day_dependency(line))
+        .collect();
+
+    let dependencies = lines.iter()
+        .position(|&line| line == "[dependencies]")
+        .ok_or("No [dependencies] section")?;
+    // At the end of the section, before any blank lines leading up to the next one
+    let mut end = lines[dependencies + 1..].iter()
+        .position(|line| line.starts_with('['))
+        .map_or(lines.len(), |i| dependencies + 1 + i);
+    while lines[end - 1].trim().is_empty() {
+        end -= 1;
+    }
+
+    let mut block = vec![String::new(), GENERATED_MARKER.to_string()];
+    let mut year = None;
+    let mut days: Vec<_> = days.iter().filter(|day| day.parts.is_ok()).collect();
+    days.sort_by_key(|day| (std::cmp::Reverse(day.year), day.day));
+    for day in days {
+        if year.is_some_and(|year| year != day.year) {
+            block.push(String::new());
+        }
+        year = Some(day.year);
+        block.push(format!(
+            "{} = {{ version = \"0.1.0\", path = \"{to_workspace}/{}\" }}",
+            day.name(),
+            day.path,
+        ));
+    }
+    let mut output: Vec<String> = lines[..end].iter().map(|line| line.to_string()).collect();
+    output.extend(block);
+    if end < lines.len() {
+        output.push(String::new());
+    }
+    output.extend(lines[end..].iter().map(|line| line.to_string()));
+
+    // Removing the old dependencies can leave runs of blank lines behind, or
+    // a blank line at the start of the section
+    output.dedup_by(|a, b| a.is_empty() && (b.is_empty() || b.starts_with('[')));
+    Ok(output.join("\n") + "\n")
+}
+
+/// Day crates with a library that aren't dependencies in `manifest`, which
+/// `sync_dependencies` would add
+pub fn missing_dependencies<'a>(manifest: &toml::Table, days: &'a [DayCrate]) -> Vec<&'a DayCrate> {
+    let dependencies = manifest.get("dependencies").and_then(|deps| deps.as_table());
+    days.iter()
+        .filter(|day| day.parts.is_ok())
+        .filter(|day| !dependencies.is_some_and(|deps| deps.contains_key(&day.name())))
+        .collect()
+}
+
+#[test]
+fn test_aoc_sol_parts() {
+    let parts = |lib: &str| aoc_sol_parts(lib).map(|parts| parts.join(" "));
+    assert_eq!(parts("aoc_tools::aoc_sol!(day06 2024: part1, part2);").as_deref(), Some("part1 part2"));
+    assert_eq!(parts("aoc_sol! {\n    day25 2019 test:\n        part1,\n}").as_deref(), Some("part1"));
+    assert_eq!(
+        parts("aoc_tools::aoc_sol!(day14 2024: part1, part2; params: Params; examples: small);").as_deref(),
+        Some("part1 part2"),
+    );
+    assert_eq!(parts("// aoc_tools::aoc_sol!(day01: part1);\npub fn part1(input: &str) {}"), None);
+}
+
+#[test]
+fn test_with_dependencies() {
+    let day = |year, day, parts: Result<Vec<String>, String>| {
+        DayCrate { year, day, path: format!("{year}/day{day:02}"), parts }
+    };
+    let days = [
+        day(2019, 1, Ok(vec!["part1".to_string()])),
+        day(2019, 2, Err("no library target (main.rs only)".to_string())),
+        day(2019, 3, Ok(vec!["part1".to_string()])),
+        day(2024, 6, Ok(vec!["part1".to_string()])),
+    ];
+    let manifest = "\
+[package]
+name = \"aoc\"
+
+[dependencies]
+day01-2018 = { version = \"0.1.0\", path = \"../../2018/day01\" }
+
+aoc_tools = { path = \"..\" }
+
+day02-2018 = { version = \"0.1.0\", path = \"../../2018/day01\" }
+
+[build-dependencies]
+toml = \"0.8\"
+";
+    let synced = with_dependencies(manifest, "../..", &days).unwrap();
+    assert_eq!(synced, format!("\
+[package]
+name = \"aoc\"
+
+[dependencies]
+aoc_tools = {{ path = \"..\" }}
+
+{GENERATED_MARKER}
+day06-2024 = {{ version = \"0.1.0\", path = \"../../2024/day06\" }}
+
+day01-2019 = {{ version = \"0.1.0\", path = \"../../2019/day01\" }}
+day03-2019 = {{ version = \"0.1.0\", path = \"../../2019/day03\" }}
+
+[build-dependencies]
+toml = \"0.8\"
+"));
+    assert_eq!(with_dependencies(&synced, "../..", &days).unwrap(), synced);
+
+    let table: toml::Table = synced.parse().unwrap();
+    assert!(missing_dependencies(&table, &days).is_empty());
+    assert_eq!(missing_dependencies(&manifest.parse().unwrap(), &days).len(), 3);
+}
@@ -0,0 +1,90 @@
+use std::path::Path;
+
+use aoc_workspace::{day_crates, parse_day_path, sync_dependencies, DayCrate};
+
+const WORKSPACE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../..");
+
+const USAGE: &str = "\
+Usage:
+    aoc-workspace sync                Point the runner's and bench's dependencies at every day crate
+    aoc-workspace new <year> <day>    Create a day crate from tools/template, then sync";
+
+/// Manifests that depend on every day crate, and their paths back to the workspace
+const DEPENDENT_MANIFESTS: &[(&str, &str)] = &[
+    ("tools/runner/Cargo.toml", "../.."),
+    ("bench/Cargo.toml", ".."),
+];
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(|arg| arg.as_str()).collect();
+    let result = match args[..] {
+        ["sync"] => sync(),
+        ["new", year, day] => new_day(year, day).and_then(|()| sync()),
+        _ => {
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        },
+    };
+    if let Err(e) = result {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+}
+
+fn sync() -> Result<(), String> {
+    let workspace_dir = Path::new(WORKSPACE_DIR);
+    let days = day_crates(workspace_dir)?;
+    for (manifest, to_workspace) in DEPENDENT_MANIFESTS {
+        if sync_dependencies(&workspace_dir.join(manifest), to_workspace, &days)? {
+            println!("Updated {manifest}");
+        }
+    }
+    for day in &days {
+        if let Err(reason) = &day.parts {
+            println!("Skipped {}: {reason}", day.name());
+        }
+    }
+    Ok(())
+}
+
+fn new_day(year: &str, day: &str) -> Result<(), String> {
+    let path = format!("{year}/day{:0>2}", day.trim_start_matches("day"));
+    let Some((year, day)) = parse_day_path(&path).filter(|&(year, day)| year >= 2015 && (1..=25).contains(&day)) else {
+        return Err(format!("Invalid year or day in `{path}`. Make sure you use all 4 digits of the year"));
+    };
+    let day = DayCrate { year, day, path, parts: Ok(vec![]) };
+
+    let workspace_dir = Path::new(WORKSPACE_DIR);
+    let day_dir = workspace_dir.join(&day.path);
+    if day_dir.exists() {
+        return Err(format!("Directory for year {year} day {:02} (./{}) already exists", day.day, day.path));
+    }
+    let fill_in = |template: String| template
+        .replace("YYYY", &year.to_string())
+        .replace("DD", &format!("{:02}", day.day));
+    for file in ["Cargo.toml", "src/lib.rs", "src/main.rs"] {
+        let template = std::fs::read_to_string(workspace_dir.join("tools/template").join(file))
+            .map_err(|e| format!("Could not read template {file}: {e}"))?;
+        let target = day_dir.join(file);
+        std::fs::create_dir_all(target.parent().unwrap())
+            .and_then(|()| std::fs::write(&target, fill_in(template)))
+            .map_err(|e| format!("Could not write {}: {e}", target.display()))?;
+    }
+    add_member(&workspace_dir.join("Cargo.toml"), &day.path)?;
+    println!("Created {}", day.path);
+    Ok(())
+}
+
+/// Adds `path` to the end of the workspace's members, unless it's there already
+fn add_member(manifest_path: &Path, path: &str) -> Result<(), String> {
+    let contents = std::fs::read_to_string(manifest_path)
+        .map_err(|e| format!("Could not read {}: {e}", manifest_path.display()))?;
+    if contents.contains(&format!("\"{path}\"")) {
+        return Ok(());
+    }
+    let members = contents.find("members = [").ok_or("The workspace has no members list")?;
+    let end = members + contents[members..].find("\n]").ok_or("Unterminated workspace members list")?;
+    let contents = format!("{}\n    \"{path}\",{}", &contents[..end], &contents[end..]);
+    std::fs::write(manifest_path, contents).map_err(|e| format!("Could not write {}: {e}", manifest_path.display()))
+}